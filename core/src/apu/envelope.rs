@@ -1,4 +1,4 @@
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 const STEP_UNIT_CYCLES: u64 = super::SAMPLE_RATE / 64;
 
@@ -53,3 +53,11 @@ impl Envelop {
         self.bits = value;
     }
 }
+
+snapshot::fields!(Envelop {
+    bits,
+    volume,
+    increases,
+    step_length,
+    cycles,
+});
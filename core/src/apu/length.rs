@@ -1,3 +1,5 @@
+use crate::snapshot;
+
 const UNIT_CYCLES: u64 = super::SAMPLE_RATE / 256;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -42,3 +44,9 @@ impl Length {
         self.rest_cycles = (self.max - ((value as u16) & (self.max - 1))) as u64 * UNIT_CYCLES;
     }
 }
+
+snapshot::fields!(Length {
+    max,
+    is_enabled,
+    rest_cycles,
+});
@@ -3,6 +3,7 @@ mod length;
 mod noise;
 mod rect_wave;
 mod sweep;
+mod tone;
 mod wave;
 
 pub const SAMPLE_RATE: u64 = 2 * 1024 * 1024;
@@ -11,9 +12,11 @@ pub const MAX_FRAME_VOLUME: u16 = 0xF * 4 * 0b111;
 
 use noise::Noise;
 use rect_wave::RectWave;
+use tone::Tone;
 use wave::Wave;
 
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
+use std::cmp::min;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct AudioFrame {
@@ -25,6 +28,13 @@ pub trait AudioTerminal {
     fn output(&mut self, frame: AudioFrame);
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NoAudioTerminal;
+
+impl AudioTerminal for NoAudioTerminal {
+    fn output(&mut self, _: AudioFrame) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct TerminalControl {
     pub is_enabled: bool,
@@ -61,37 +71,55 @@ pub struct Apu {
     rect_wave2: RectWave,
     wave: Wave,
     noise: Noise,
+    tone: Tone,
 }
 
-impl Default for Apu {
-    fn default() -> Self {
-        let mut rect_wave1 = RectWave::default();
-        rect_wave1.set_length_wave_bits(0xBF);
-        rect_wave1.set_envelope_bits(0xF3);
+impl Apu {
+    pub fn new(plays_boot_sound: bool) -> Self {
         Self {
             is_enabled: true,
             left_control: Default::default(),
             right_control: Default::default(),
             output_terminal_selection: 0xF3,
-            rect_wave1,
+            rect_wave1: RectWave::post_boot(plays_boot_sound),
             rect_wave2: Default::default(),
             wave: Default::default(),
             noise: Default::default(),
+            tone: Default::default(),
         }
     }
-}
 
-impl Apu {
+    pub fn power_on() -> Self {
+        let mut result = Self {
+            is_enabled: false,
+            output_terminal_selection: 0x00,
+            rect_wave1: Default::default(),
+            ..Self::new(false)
+        };
+        result.set_channel_control_bits(0x00);
+        result
+    }
+
+    pub fn set_tone(&mut self, value: Option<u8>) {
+        self.tone.set_value(value);
+    }
+
     pub fn tick(&mut self, terminal: &mut impl AudioTerminal) {
-        if self.is_enabled {
+        let frame = if self.is_enabled {
             self.rect_wave1.tick();
             self.rect_wave2.tick();
             self.wave.tick();
             self.noise.tick();
-            terminal.output(self.frame());
+            self.frame()
         } else {
-            terminal.output(AudioFrame::default());
-        }
+            AudioFrame::default()
+        };
+        self.tone.tick();
+        let tone = self.tone.output();
+        terminal.output(AudioFrame {
+            left: min(frame.left + tone, MAX_FRAME_VOLUME),
+            right: min(frame.right + tone, MAX_FRAME_VOLUME),
+        });
     }
 
     fn frame(&self) -> AudioFrame {
@@ -192,3 +220,16 @@ impl Apu {
         }
     }
 }
+
+snapshot::fields!(TerminalControl { is_enabled, level });
+
+snapshot::fields!(Apu {
+    is_enabled,
+    left_control,
+    right_control,
+    output_terminal_selection,
+    rect_wave1,
+    rect_wave2,
+    wave,
+    noise,
+});
@@ -1,6 +1,6 @@
 use super::envelope::Envelop;
 use super::length::Length;
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Noise {
@@ -116,3 +116,14 @@ impl Noise {
         }
     }
 }
+
+snapshot::fields!(Noise {
+    is_started,
+    random,
+    length,
+    envelope,
+    frequency_shift,
+    is_short,
+    division_ratio,
+    cycles,
+});
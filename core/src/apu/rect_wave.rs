@@ -1,7 +1,7 @@
 use super::envelope::Envelop;
 use super::length::Length;
 use super::sweep::Sweep;
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 const DUTY_CYCLE_LENGTH: usize = 8;
 
@@ -73,6 +73,16 @@ impl Default for RectWave {
 }
 
 impl RectWave {
+    pub fn post_boot(is_started: bool) -> Self {
+        let mut result = Self {
+            is_started,
+            ..Default::default()
+        };
+        result.set_length_wave_bits(0xBF);
+        result.set_envelope_bits(0xF3);
+        result
+    }
+
     fn start(&mut self) {
         self.is_started = true;
         self.cycles = 0;
@@ -164,3 +174,16 @@ impl RectWave {
         }
     }
 }
+
+snapshot::fields!(DutyCycle { bits });
+
+snapshot::fields!(RectWave {
+    is_started,
+    duty_cycle,
+    duty_cycle_step,
+    length,
+    frequency,
+    sweep,
+    envelope,
+    cycles,
+});
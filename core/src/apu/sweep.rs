@@ -1,4 +1,4 @@
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 const STEP_UNIT_CYCLES: u64 = super::SAMPLE_RATE / 128;
 
@@ -42,3 +42,10 @@ impl Sweep {
         self.shift_amount = value & 0b111;
     }
 }
+
+snapshot::fields!(Sweep {
+    step_length,
+    decreases,
+    shift_amount,
+    cycles,
+});
@@ -0,0 +1,39 @@
+use super::{MAX_FRAME_VOLUME, SAMPLE_RATE};
+
+const FREQUENCY_STEP: u64 = 256;
+
+const VOLUME: u16 = MAX_FRAME_VOLUME / 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Tone {
+    value: Option<u8>,
+    cycles: u64,
+}
+
+impl Tone {
+    pub fn set_value(&mut self, value: Option<u8>) {
+        let value = value.filter(|value| *value != 0);
+        if self.value != value {
+            self.value = value;
+            self.cycles = 0;
+        }
+    }
+
+    fn period(&self) -> Option<u64> {
+        self.value
+            .map(|value| SAMPLE_RATE / (FREQUENCY_STEP * value as u64))
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(period) = self.period() {
+            self.cycles = (self.cycles + 1) % period;
+        }
+    }
+
+    pub fn output(&self) -> u16 {
+        match self.period() {
+            Some(period) if self.cycles < period / 2 => VOLUME,
+            _ => 0,
+        }
+    }
+}
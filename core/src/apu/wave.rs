@@ -1,5 +1,5 @@
 use super::length::Length;
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 const PATTERN_FRAME_COUNT: usize = 32;
 
@@ -130,3 +130,14 @@ impl Wave {
         &mut self.pattern
     }
 }
+
+snapshot::fields!(Wave {
+    is_enabled,
+    is_started,
+    length,
+    level,
+    pattern,
+    step,
+    frequency,
+    cycles,
+});
@@ -0,0 +1,100 @@
+use crate::{snapshot, util::bits::Bits};
+
+const DMG_SIZE: usize = 0x100;
+
+const CGB_SIZE: usize = 0x900;
+
+const CARTRIDGE_HEADER_RANGE: std::ops::Range<usize> = 0x100..0x200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    InvalidSize(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BootRom {
+    data: Vec<u8>,
+}
+
+impl BootRom {
+    pub fn new(data: Vec<u8>) -> Result<Self, Error> {
+        match data.len() {
+            DMG_SIZE | CGB_SIZE => Ok(Self { data }),
+            size => Err(Error::InvalidSize(size)),
+        }
+    }
+
+    pub fn is_cgb(&self) -> bool {
+        self.data.len() == CGB_SIZE
+    }
+
+    fn read(&self, address: u16) -> Option<u8> {
+        let address = address as usize;
+        if CARTRIDGE_HEADER_RANGE.contains(&address) {
+            None
+        } else {
+            self.data.get(address).copied()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BootRomOverlay {
+    boot_rom: Option<BootRom>,
+    is_mapped: bool,
+    compatibility_bits: u8,
+}
+
+impl BootRomOverlay {
+    pub fn new(boot_rom: Option<BootRom>) -> Self {
+        Self {
+            is_mapped: boot_rom.is_some(),
+            boot_rom,
+            compatibility_bits: 0x00,
+        }
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        self.is_mapped
+    }
+
+    pub fn is_cgb(&self) -> bool {
+        self.boot_rom.as_ref().is_some_and(BootRom::is_cgb)
+    }
+
+    pub fn is_dmg_compatible(&self) -> bool {
+        self.is_cgb() && !self.is_mapped && self.compatibility_bits.bit(2)
+    }
+
+    pub fn set_compatibility_bits(&mut self, value: u8) {
+        if self.is_cgb() && self.is_mapped {
+            self.compatibility_bits = value;
+        }
+    }
+
+    pub fn read(&self, address: u16) -> Option<u8> {
+        self.boot_rom
+            .as_ref()
+            .filter(|_| self.is_mapped)
+            .and_then(|boot_rom| boot_rom.read(address))
+    }
+
+    pub fn bits(&self) -> u8 {
+        if self.boot_rom.is_some() {
+            0b1111_1110 | !self.is_mapped as u8
+        } else {
+            0xFF
+        }
+    }
+
+    pub fn set_bits(&mut self, value: u8) {
+        if value != 0x00 {
+            self.is_mapped = false;
+        }
+    }
+}
+
+snapshot::fields!(BootRomOverlay {
+    is_mapped,
+    compatibility_bits,
+});
@@ -47,7 +47,7 @@ fn decode(code: u8) -> (MbcType, Vec<CartridgeOption>) {
         0x22 => (Mbc7, vec![Ram, Battery, Rumble, Sensor]),
         0xFC => (PocketCamera, vec![]),
         0xFD => (BandaiTama5, vec![]),
-        0xFE => (Huc3, vec![]),
+        0xFE => (Huc3, vec![Ram, Battery, Timer]),
         0xFF => (Huc1, vec![Ram, Battery]),
         _ => (Unknown, vec![]),
     }
@@ -206,6 +206,13 @@ impl Licensee {
         self.new_code.as_deref()
     }
 
+    pub fn is_nintendo(&self) -> bool {
+        match &self.new_code {
+            Some(new_code) => new_code == b"01",
+            None => self.old_code == 0x01,
+        }
+    }
+
     pub fn name(&self) -> &str {
         self.new_code.as_ref().map_or_else(
             || name_from_old_code(self.old_code),
@@ -8,6 +8,12 @@ pub struct Logo {
 
 const RANGE: RangeInclusive<usize> = 0x0104..=0x0133;
 
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
 impl Logo {
     pub fn load(rom: &[u8]) -> Self {
         Logo {
@@ -19,6 +25,10 @@ impl Logo {
         &self.bytes
     }
 
+    pub fn is_valid(&self) -> bool {
+        self.bytes == NINTENDO_LOGO
+    }
+
     pub fn decode(&self) -> Vec<Vec<bool>> {
         const COLUMNS: usize = 12;
         const ROWS: usize = 2;
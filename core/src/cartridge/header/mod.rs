@@ -13,13 +13,13 @@ mod title;
 mod version;
 
 pub use cartridge_type::{CartridgeOption, CartridgeType, MbcType};
-pub use cgb_flag::CgbFlag;
+pub use cgb_flag::{CgbFlag, CgbSupport};
 pub use destination::Destination;
 pub use entry_point::EntryPoint;
 pub use global_checksum::GlobalChecksum;
 pub use header_checksum::HeaderChecksum;
 pub use licensee::Licensee;
-pub use logo::Logo;
+pub use logo::{Logo, NINTENDO_LOGO};
 pub use ram_size::RamSize;
 pub use rom_size::RomSize;
 pub use sgb_flag::SgbFlag;
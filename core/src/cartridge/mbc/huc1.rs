@@ -0,0 +1,75 @@
+use super::{Mbc, MbcContext};
+use crate::snapshot;
+
+const IR_MODE: u8 = 0x0E;
+
+const IR_NO_LIGHT: u8 = 0xC0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Huc1 {
+    rom_bank_number: usize,
+    ram_bank_number: usize,
+    ir_enabled: bool,
+}
+
+impl Default for Huc1 {
+    fn default() -> Self {
+        Self {
+            rom_bank_number: 1,
+            ram_bank_number: 0,
+            ir_enabled: false,
+        }
+    }
+}
+
+impl Mbc for Huc1 {
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
+    fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
+            0x4000..=0x7FFF => context.read_from_rom_bank(self.rom_bank_number, address - 0x4000),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_rom(&mut self, _: &mut dyn MbcContext, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                self.ir_enabled = value & 0xF == IR_MODE;
+            }
+            0x2000..=0x3FFF => {
+                self.rom_bank_number = (value & 0b0011_1111) as usize;
+            }
+            0x4000..=0x5FFF => {
+                self.ram_bank_number = (value & 0b11) as usize;
+            }
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, context: &dyn MbcContext, address: u16) -> u8 {
+        if self.ir_enabled {
+            IR_NO_LIGHT
+        } else {
+            context.read_from_ram_bank(self.ram_bank_number, address)
+        }
+    }
+
+    fn write_ram(&mut self, context: &mut dyn MbcContext, address: u16, value: u8) {
+        if !self.ir_enabled {
+            context.write_to_ram_bank(self.ram_bank_number, address, value);
+        }
+    }
+}
+
+snapshot::fields!(Huc1 {
+    rom_bank_number,
+    ram_bank_number,
+    ir_enabled,
+});
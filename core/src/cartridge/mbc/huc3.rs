@@ -0,0 +1,226 @@
+use super::{Mbc, MbcContext};
+use crate::{cartridge::rtc::Clock, snapshot, M_CYCLES};
+
+const RAM_READ_ONLY_MODE: u8 = 0x0;
+
+const RAM_MODE: u8 = 0xA;
+
+const RTC_WRITE_MODE: u8 = 0xB;
+
+const RTC_READ_MODE: u8 = 0xC;
+
+const RTC_SEMAPHORE_MODE: u8 = 0xD;
+
+const IR_MODE: u8 = 0xE;
+
+const IR_NO_LIGHT: u8 = 0xC0;
+
+const RTC_READY: u8 = 0x01;
+
+const TONE_ADDRESS: usize = 0x27;
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+
+const M_CYCLES_PER_MINUTE: u64 = SECONDS_PER_MINUTE as u64 * M_CYCLES;
+
+const RTC_FOOTER_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Huc3 {
+    rom_bank_number: usize,
+    ram_bank_number: usize,
+    mode: u8,
+    command: u8,
+    response: u8,
+    access_address: usize,
+    memory: [u8; 0x100],
+    minutes: u16,
+    days: u16,
+    m_cycles: u64,
+    clock: Clock,
+    timestamp: i64,
+}
+
+impl Default for Huc3 {
+    fn default() -> Self {
+        Self {
+            rom_bank_number: 1,
+            ram_bank_number: 0,
+            mode: RAM_READ_ONLY_MODE,
+            command: 0,
+            response: 0,
+            access_address: 0,
+            memory: [0; 0x100],
+            minutes: 0,
+            days: 0,
+            m_cycles: 0,
+            clock: Default::default(),
+            timestamp: 0,
+        }
+    }
+}
+
+impl Huc3 {
+    fn write_nibbles(&mut self, address: usize, value: u16) {
+        for index in 0..3 {
+            self.memory[address + index] = (value >> (index * 4) & 0xF) as u8;
+        }
+    }
+
+    fn read_nibbles(&self, address: usize) -> u16 {
+        (0..3).fold(0, |value, index| {
+            value | (self.memory[address + index] as u16) << (index * 4)
+        })
+    }
+
+    fn advance(&mut self, minutes: u64) {
+        let total = self.minutes as u64 + minutes;
+        self.minutes = (total % MINUTES_PER_DAY as u64) as u16;
+        self.days = (self.days as u64 + total / MINUTES_PER_DAY as u64) as u16 & 0xFFF;
+    }
+
+    fn execute_extended_command(&mut self, argument: u8) {
+        match argument {
+            0x0 => {
+                self.write_nibbles(0x00, self.minutes);
+                self.write_nibbles(0x03, self.days);
+            }
+            0x1 => {
+                self.minutes = self.read_nibbles(0x00) % MINUTES_PER_DAY;
+                self.days = self.read_nibbles(0x03);
+                self.m_cycles = 0;
+            }
+            0x2 => self.response = RTC_READY,
+            _ => {}
+        }
+    }
+
+    fn execute_command(&mut self, value: u8) {
+        self.command = value >> 4 & 0b111;
+        let argument = value & 0xF;
+        match self.command {
+            0x1 => {
+                self.response = self.memory[self.access_address];
+                self.access_address = (self.access_address + 1) & 0xFF;
+            }
+            0x3 => {
+                self.memory[self.access_address] = argument;
+                self.access_address = (self.access_address + 1) & 0xFF;
+            }
+            0x4 => self.access_address = self.access_address & 0xF0 | argument as usize,
+            0x5 => self.access_address = (argument as usize) << 4 | self.access_address & 0x0F,
+            0x6 => self.execute_extended_command(argument),
+            _ => {}
+        }
+    }
+}
+
+impl Mbc for Huc3 {
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.m_cycles += 1;
+        if self.m_cycles < M_CYCLES_PER_MINUTE {
+            return;
+        }
+        self.m_cycles = 0;
+        self.timestamp += SECONDS_PER_MINUTE;
+        self.advance(1);
+    }
+
+    fn set_rtc_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+        self.timestamp = clock.now().unwrap_or_default();
+    }
+
+    fn rtc_footer(&self) -> Option<Vec<u8>> {
+        let mut footer = self.timestamp.to_le_bytes().to_vec();
+        footer.extend(self.minutes.to_le_bytes());
+        footer.extend(self.days.to_le_bytes());
+        Some(footer)
+    }
+
+    fn load_rtc_footer(&mut self, footer: &[u8]) -> bool {
+        if footer.len() != RTC_FOOTER_LENGTH {
+            return false;
+        }
+        let saved_timestamp = i64::from_le_bytes(footer[0..8].try_into().unwrap());
+        self.minutes = u16::from_le_bytes(footer[8..10].try_into().unwrap()) % MINUTES_PER_DAY;
+        self.days = u16::from_le_bytes(footer[10..12].try_into().unwrap()) & 0xFFF;
+        let elapsed_minutes = self
+            .clock
+            .now()
+            .map_or(0, |now| (now - saved_timestamp).max(0) / SECONDS_PER_MINUTE);
+        self.advance(elapsed_minutes as u64);
+        self.timestamp = saved_timestamp + elapsed_minutes * SECONDS_PER_MINUTE;
+        true
+    }
+
+    fn tone(&self) -> Option<u8> {
+        Some(self.memory[TONE_ADDRESS]).filter(|tone| *tone != 0)
+    }
+
+    fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
+            0x4000..=0x7FFF => context.read_from_rom_bank(self.rom_bank_number, address - 0x4000),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_rom(&mut self, _: &mut dyn MbcContext, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                self.mode = value & 0xF;
+            }
+            0x2000..=0x3FFF => {
+                self.rom_bank_number = (value & 0b0111_1111) as usize;
+            }
+            0x4000..=0x5FFF => {
+                self.ram_bank_number = (value & 0xF) as usize;
+            }
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, context: &dyn MbcContext, address: u16) -> u8 {
+        match self.mode {
+            RAM_READ_ONLY_MODE | RAM_MODE => {
+                context.read_from_ram_bank(self.ram_bank_number, address)
+            }
+            RTC_READ_MODE => self.command << 4 | self.response,
+            RTC_SEMAPHORE_MODE => RTC_READY,
+            IR_MODE => IR_NO_LIGHT,
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, context: &mut dyn MbcContext, address: u16, value: u8) {
+        match self.mode {
+            RAM_MODE => context.write_to_ram_bank(self.ram_bank_number, address, value),
+            RTC_WRITE_MODE => self.execute_command(value),
+            _ => {}
+        }
+    }
+}
+
+snapshot::fields!(Huc3 {
+    rom_bank_number,
+    ram_bank_number,
+    mode,
+    command,
+    response,
+    access_address,
+    memory,
+    minutes,
+    days,
+    m_cycles,
+    timestamp,
+});
@@ -1,4 +1,8 @@
 use super::{Mbc, MbcContext};
+use crate::{
+    cartridge::header::Logo,
+    snapshot::{self, Error, Reader, Snapshot, Writer},
+};
 use std::cmp::max;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -17,12 +21,17 @@ impl From<u8> for BankingMode {
     }
 }
 
+const MULTICART_ROM_SIZE: usize = 0x10_0000;
+
+const MULTICART_GAME_SIZE: usize = 0x4_0000;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Mbc1 {
     rom_bank_number_lower: usize,
     ram_bank_number_or_rom_bank_number_upper: usize,
     ram_enabled: bool,
     banking_mode: BankingMode,
+    is_multicart: bool,
 }
 
 impl Default for Mbc1 {
@@ -32,21 +41,53 @@ impl Default for Mbc1 {
             ram_bank_number_or_rom_bank_number_upper: 0,
             ram_enabled: false,
             banking_mode: BankingMode::Simple,
+            is_multicart: false,
         }
     }
 }
 
 impl Mbc1 {
+    pub fn multicart() -> Self {
+        Self {
+            is_multicart: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_multicart(rom: &[u8]) -> bool {
+        rom.len() == MULTICART_ROM_SIZE
+            && rom
+                .get(MULTICART_GAME_SIZE..)
+                .is_some_and(|game| Logo::load(game).is_valid())
+    }
+
+    fn upper_shift(&self) -> usize {
+        if self.is_multicart {
+            4
+        } else {
+            5
+        }
+    }
+
+    fn lower_mask(&self) -> usize {
+        if self.is_multicart {
+            0b0000_1111
+        } else {
+            0b0001_1111
+        }
+    }
+
     fn first_rom_bank_number(&self) -> usize {
         use BankingMode::*;
         match self.banking_mode {
             Simple => 0,
-            Advanced => self.ram_bank_number_or_rom_bank_number_upper << 5,
+            Advanced => self.ram_bank_number_or_rom_bank_number_upper << self.upper_shift(),
         }
     }
 
     fn rom_bank_number(&self) -> usize {
-        self.ram_bank_number_or_rom_bank_number_upper << 5 | self.rom_bank_number_lower
+        self.ram_bank_number_or_rom_bank_number_upper << self.upper_shift()
+            | self.rom_bank_number_lower & self.lower_mask()
     }
 
     fn ram_bank_number(&self) -> usize {
@@ -59,6 +100,13 @@ impl Mbc1 {
 }
 
 impl Mbc for Mbc1 {
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => self.first_rom_bank_number(),
+            _ => self.rom_bank_number(),
+        }
+    }
+
     fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
         match address {
             0x0000..=0x3FFF => context.read_from_rom_bank(self.first_rom_bank_number(), address),
@@ -100,4 +148,22 @@ impl Mbc for Mbc1 {
     }
 }
 
-// TODO MBC1m?
+impl Snapshot for BankingMode {
+    fn save(&self, writer: &mut Writer) {
+        (*self == Self::Advanced).save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        let mut is_advanced = false;
+        is_advanced.load(reader)?;
+        *self = (is_advanced as u8).into();
+        Ok(())
+    }
+}
+
+snapshot::fields!(Mbc1 {
+    rom_bank_number_lower,
+    ram_bank_number_or_rom_bank_number_upper,
+    ram_enabled,
+    banking_mode,
+});
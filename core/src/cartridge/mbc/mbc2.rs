@@ -1,4 +1,5 @@
 use super::{Mbc, MbcContext};
+use crate::snapshot;
 use std::cmp::max;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,6 +22,13 @@ impl Mbc for Mbc2 {
         0x200
     }
 
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
     fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
         match address {
             0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
@@ -56,3 +64,8 @@ impl Mbc for Mbc2 {
         }
     }
 }
+
+snapshot::fields!(Mbc2 {
+    rom_bank_number,
+    ram_enabled,
+});
@@ -1,44 +1,97 @@
 use super::{Mbc, MbcContext};
-use crate::util::bits::Bits;
-use chrono::prelude::*;
+use crate::{
+    cartridge::{
+        header::Header,
+        rtc::{self, Clock, Rtc},
+    },
+    snapshot::{Error, Reader, Snapshot, Writer},
+};
 use std::cmp::max;
 
+const MBC3_MAX_ROM_SIZE: usize = 0x20_0000;
+
+const MBC3_MAX_RAM_SIZE: usize = 0x8000;
+
 #[derive(Debug, Clone)]
 pub struct Mbc3 {
     rom_bank_number: usize,
     ram_and_rtc_enabled: bool,
     ram_bank_number_or_rtc_register_select: usize,
-    latched_datetime: DateTime<Local>,
     ready_to_latch: bool,
-    day_counter: u64,
-    rtc_halt: bool,
-    day_counter_carry: bool,
+    rtc: Rtc,
+    has_rtc: bool,
+    is_mbc30: bool,
 }
 
-impl Default for Mbc3 {
-    fn default() -> Self {
+impl Mbc3 {
+    pub fn new(has_rtc: bool) -> Self {
         Self {
             rom_bank_number: 1,
             ram_and_rtc_enabled: false,
             ram_bank_number_or_rtc_register_select: 0,
-            latched_datetime: Local::now(),
             ready_to_latch: false,
-            day_counter: 0,
-            rtc_halt: false,
-            day_counter_carry: false,
+            rtc: Default::default(),
+            has_rtc,
+            is_mbc30: false,
         }
     }
-}
 
-impl Mbc3 {
-    fn set_datetime(&mut self, value: Option<DateTime<Local>>) {
-        if let Some(value) = value {
-            self.latched_datetime = value;
+    pub fn mbc30(has_rtc: bool) -> Self {
+        Self {
+            is_mbc30: true,
+            ..Self::new(has_rtc)
+        }
+    }
+
+    pub fn is_mbc30(header: &Header) -> bool {
+        header.rom_size.amount() > MBC3_MAX_ROM_SIZE || header.ram_size.amount() > MBC3_MAX_RAM_SIZE
+    }
+
+    fn rom_bank_mask(&self) -> u8 {
+        if self.is_mbc30 {
+            0b1111_1111
+        } else {
+            0b0111_1111
         }
     }
+
+    fn ram_bank_number(&self) -> Option<usize> {
+        let max_ram_bank_number = if self.is_mbc30 { 0x07 } else { 0x03 };
+        Some(self.ram_bank_number_or_rtc_register_select)
+            .filter(|bank_number| *bank_number <= max_ram_bank_number)
+    }
 }
 
 impl Mbc for Mbc3 {
+    fn tick(&mut self) {
+        if self.has_rtc {
+            self.rtc.tick();
+        }
+    }
+
+    fn set_rtc_clock(&mut self, clock: Clock) {
+        self.rtc.set_clock(clock);
+    }
+
+    fn rtc_footer(&self) -> Option<Vec<u8>> {
+        Some(self.rtc.footer()).filter(|_| self.has_rtc)
+    }
+
+    fn load_rtc_footer(&mut self, footer: &[u8]) -> bool {
+        if !self.has_rtc || !rtc::is_footer(footer) {
+            return false;
+        }
+        self.rtc.load_footer(footer);
+        true
+    }
+
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
     fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
         match address {
             0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
@@ -53,27 +106,14 @@ impl Mbc for Mbc3 {
                 self.ram_and_rtc_enabled = value & 0xF == 0xA;
             }
             0x2000..=0x3FFF => {
-                self.rom_bank_number = max(1, value & 0b0111_1111) as usize;
+                self.rom_bank_number = max(1, value & self.rom_bank_mask()) as usize;
             }
             0x4000..=0x5FFF => {
                 self.ram_bank_number_or_rtc_register_select = value as usize;
             }
             0x6000..=0x7FFF => {
                 if self.ready_to_latch && value == 0x01 {
-                    let now = Local::now();
-                    let days = self
-                        .latched_datetime
-                        .date()
-                        .signed_duration_since(now.date())
-                        .num_days();
-                    if days > 0 {
-                        self.day_counter = self.day_counter.wrapping_add(days as u64);
-                        if self.day_counter >= 512 {
-                            self.day_counter_carry = true;
-                            self.day_counter %= 512;
-                        }
-                    }
-                    self.latched_datetime = now;
+                    self.rtc.latch();
                 }
                 self.ready_to_latch = value == 0x00;
             }
@@ -85,51 +125,45 @@ impl Mbc for Mbc3 {
         if !self.ram_and_rtc_enabled {
             return 0xFF;
         }
-        match self.ram_bank_number_or_rtc_register_select {
-            0x00..=0x03 => {
-                context.read_from_ram_bank(self.ram_bank_number_or_rtc_register_select, address)
-            }
-            0x08 => self.latched_datetime.second() as u8,
-            0x09 => self.latched_datetime.minute() as u8,
-            0x0A => self.latched_datetime.hour() as u8,
-            0x0B => (self.day_counter & 0xFF) as u8,
-            0x0C => {
-                ((self.day_counter >> 8) & 0b1) as u8
-                    | (self.rtc_halt as u8) << 6
-                    | (self.day_counter_carry as u8) << 7
-            }
-            _ => 0xFF,
+        if let Some(bank_number) = self.ram_bank_number() {
+            return context.read_from_ram_bank(bank_number, address);
+        }
+        if !self.has_rtc {
+            return 0xFF;
         }
+        self.rtc.read(self.ram_bank_number_or_rtc_register_select)
     }
 
     fn write_ram(&mut self, context: &mut dyn MbcContext, address: u16, value: u8) {
         if !self.ram_and_rtc_enabled {
             return;
         }
-        match self.ram_bank_number_or_rtc_register_select {
-            0x00..=0x03 => context.write_to_ram_bank(
-                self.ram_bank_number_or_rtc_register_select,
-                address,
-                value,
-            ),
-            0x08 => {
-                self.set_datetime(self.latched_datetime.with_second(value as u32));
-            }
-            0x09 => {
-                self.set_datetime(self.latched_datetime.with_minute(value as u32));
-            }
-            0x0A => {
-                self.set_datetime(self.latched_datetime.with_hour(value as u32));
-            }
-            0x0B => {
-                self.day_counter = (self.day_counter & !0xFF) | value as u64;
-            }
-            0x0C => {
-                self.day_counter = (value as u64 & 0b1) << 8 | self.day_counter & 0xFF;
-                self.rtc_halt = value.bit(6);
-                self.day_counter_carry = value.bit(7);
-            }
-            _ => {}
+        if let Some(bank_number) = self.ram_bank_number() {
+            context.write_to_ram_bank(bank_number, address, value);
+            return;
+        }
+        if !self.has_rtc {
+            return;
         }
+        self.rtc
+            .write(self.ram_bank_number_or_rtc_register_select, value);
+    }
+}
+
+impl Snapshot for Mbc3 {
+    fn save(&self, writer: &mut Writer) {
+        self.rom_bank_number.save(writer);
+        self.ram_and_rtc_enabled.save(writer);
+        self.ram_bank_number_or_rtc_register_select.save(writer);
+        self.ready_to_latch.save(writer);
+        self.rtc.save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        self.rom_bank_number.load(reader)?;
+        self.ram_and_rtc_enabled.load(reader)?;
+        self.ram_bank_number_or_rtc_register_select.load(reader)?;
+        self.ready_to_latch.load(reader)?;
+        self.rtc.load(reader)
     }
 }
@@ -1,4 +1,5 @@
 use super::{Mbc, MbcContext};
+use crate::snapshot;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Mbc5 {
@@ -18,6 +19,13 @@ impl Default for Mbc5 {
 }
 
 impl Mbc for Mbc5 {
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
     fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
         match address {
             0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
@@ -59,3 +67,9 @@ impl Mbc for Mbc5 {
         }
     }
 }
+
+snapshot::fields!(Mbc5 {
+    rom_bank_number,
+    ram_enabled,
+    ram_bank_number,
+});
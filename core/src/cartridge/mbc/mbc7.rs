@@ -0,0 +1,330 @@
+use super::{Mbc, MbcContext};
+use crate::{
+    snapshot::{self, Error, Reader, Snapshot, Writer},
+    tilt::Tilt,
+    util::bits::Bits,
+};
+
+const EEPROM_SIZE: usize = 0x100;
+
+const EEPROM_WORD_MASK: usize = 0x7F;
+
+const EEPROM_COMMAND_LENGTH: usize = 10;
+
+const EEPROM_WORD_LENGTH: usize = 16;
+
+const ACCELEROMETER_RESET: u16 = 0x8000;
+
+const ACCELEROMETER_CENTER: f32 = 0x81D0 as f32;
+
+const ACCELEROMETER_RANGE: f32 = 0x70 as f32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EepromMode {
+    Idle,
+    Command,
+    Read,
+    Write,
+    WriteAll,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Eeprom {
+    chip_select: bool,
+    clock: bool,
+    data_in: bool,
+    data_out: bool,
+    mode: EepromMode,
+    shift: u16,
+    bit_count: usize,
+    address: usize,
+    write_enabled: bool,
+}
+
+impl Default for Eeprom {
+    fn default() -> Self {
+        Self {
+            chip_select: false,
+            clock: false,
+            data_in: false,
+            data_out: true,
+            mode: EepromMode::Idle,
+            shift: 0,
+            bit_count: 0,
+            address: 0,
+            write_enabled: false,
+        }
+    }
+}
+
+fn read_word(context: &dyn MbcContext, address: usize) -> u16 {
+    let index = address * 2;
+    u16::from_le_bytes([context.ram()[index], context.ram()[index + 1]])
+}
+
+fn write_word(context: &mut dyn MbcContext, address: usize, value: u16) {
+    let index = address * 2;
+    context.ram_mut()[index..index + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_all_words(context: &mut dyn MbcContext, value: u16) {
+    for address in 0..EEPROM_SIZE / 2 {
+        write_word(context, address, value);
+    }
+}
+
+impl Eeprom {
+    fn read(&self) -> u8 {
+        (self.chip_select as u8) << 7
+            | (self.clock as u8) << 6
+            | (self.data_in as u8) << 1
+            | self.data_out as u8
+    }
+
+    fn write(&mut self, context: &mut dyn MbcContext, value: u8) {
+        let is_rising_edge = !self.clock && value.bit(6);
+        self.chip_select = value.bit(7);
+        self.clock = value.bit(6);
+        self.data_in = value.bit(1);
+        if !self.chip_select {
+            self.mode = EepromMode::Idle;
+        } else if is_rising_edge {
+            self.clock_bit(context);
+        }
+    }
+
+    fn clock_bit(&mut self, context: &mut dyn MbcContext) {
+        use EepromMode::*;
+        match self.mode {
+            Idle => {
+                if self.data_in {
+                    self.mode = Command;
+                    self.shift = 0;
+                    self.bit_count = 0;
+                }
+            }
+            Command => {
+                self.shift_in();
+                if self.bit_count == EEPROM_COMMAND_LENGTH {
+                    self.execute(context);
+                }
+            }
+            Read => {
+                self.data_out = self.shift & 0x8000 != 0;
+                self.shift <<= 1;
+                self.bit_count += 1;
+                if self.bit_count == EEPROM_WORD_LENGTH {
+                    self.mode = Idle;
+                }
+            }
+            Write | WriteAll => {
+                self.shift_in();
+                if self.bit_count == EEPROM_WORD_LENGTH {
+                    if self.write_enabled {
+                        match self.mode {
+                            Write => write_word(context, self.address, self.shift),
+                            _ => write_all_words(context, self.shift),
+                        }
+                    }
+                    self.data_out = true;
+                    self.mode = Idle;
+                }
+            }
+        }
+    }
+
+    fn shift_in(&mut self) {
+        self.shift = self.shift << 1 | self.data_in as u16;
+        self.bit_count += 1;
+    }
+
+    fn execute(&mut self, context: &mut dyn MbcContext) {
+        let command = self.shift;
+        self.address = command as usize & EEPROM_WORD_MASK;
+        self.shift = 0;
+        self.bit_count = 0;
+        self.mode = EepromMode::Idle;
+        match (command >> 8 & 0b11, command >> 6 & 0b11) {
+            (0b10, _) => {
+                self.shift = read_word(context, self.address);
+                self.data_out = false;
+                self.mode = EepromMode::Read;
+            }
+            (0b01, _) => self.mode = EepromMode::Write,
+            (0b11, _) => {
+                if self.write_enabled {
+                    write_word(context, self.address, 0xFFFF);
+                }
+            }
+            (0b00, 0b11) => self.write_enabled = true,
+            (0b00, 0b00) => self.write_enabled = false,
+            (0b00, 0b10) => {
+                if self.write_enabled {
+                    write_all_words(context, 0xFFFF);
+                }
+            }
+            _ => self.mode = EepromMode::WriteAll,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mbc7 {
+    rom_bank_number: usize,
+    ram_enabled_1: bool,
+    ram_enabled_2: bool,
+    tilt: Tilt,
+    is_latch_ready: bool,
+    x_latch: u16,
+    y_latch: u16,
+    eeprom: Eeprom,
+}
+
+impl Default for Mbc7 {
+    fn default() -> Self {
+        Self {
+            rom_bank_number: 1,
+            ram_enabled_1: false,
+            ram_enabled_2: false,
+            tilt: Default::default(),
+            is_latch_ready: false,
+            x_latch: ACCELEROMETER_RESET,
+            y_latch: ACCELEROMETER_RESET,
+            eeprom: Default::default(),
+        }
+    }
+}
+
+impl Mbc7 {
+    fn ram_enabled(&self) -> bool {
+        self.ram_enabled_1 && self.ram_enabled_2
+    }
+}
+
+impl Mbc for Mbc7 {
+    fn internal_ram_size(&self) -> usize {
+        EEPROM_SIZE
+    }
+
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank_number,
+        }
+    }
+
+    fn set_tilt(&mut self, tilt: Tilt) {
+        self.tilt = tilt;
+    }
+
+    fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => context.read_from_rom_bank(0, address),
+            0x4000..=0x7FFF => context.read_from_rom_bank(self.rom_bank_number, address - 0x4000),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_rom(&mut self, _: &mut dyn MbcContext, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                self.ram_enabled_1 = value == 0x0A;
+            }
+            0x2000..=0x3FFF => {
+                self.rom_bank_number = (value & 0b0111_1111) as usize;
+            }
+            0x4000..=0x5FFF => {
+                self.ram_enabled_2 = value == 0x40;
+            }
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, _: &dyn MbcContext, address: u16) -> u8 {
+        if !self.ram_enabled() || address >= 0x1000 {
+            return 0xFF;
+        }
+        match address >> 4 & 0xF {
+            0x2 => (self.x_latch & 0xFF) as u8,
+            0x3 => (self.x_latch >> 8) as u8,
+            0x4 => (self.y_latch & 0xFF) as u8,
+            0x5 => (self.y_latch >> 8) as u8,
+            0x6 => 0x00,
+            0x8 => self.eeprom.read(),
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, context: &mut dyn MbcContext, address: u16, value: u8) {
+        if !self.ram_enabled() || address >= 0x1000 {
+            return;
+        }
+        match address >> 4 & 0xF {
+            0x0 if value == 0x55 => {
+                self.x_latch = ACCELEROMETER_RESET;
+                self.y_latch = ACCELEROMETER_RESET;
+                self.is_latch_ready = true;
+            }
+            0x1 if value == 0xAA && self.is_latch_ready => {
+                let latch =
+                    |value: f32| (ACCELEROMETER_CENTER + value * ACCELEROMETER_RANGE) as u16;
+                self.x_latch = latch(self.tilt.x);
+                self.y_latch = latch(self.tilt.y);
+                self.is_latch_ready = false;
+            }
+            0x8 => self.eeprom.write(context, value),
+            _ => {}
+        }
+    }
+}
+
+impl Snapshot for EepromMode {
+    fn save(&self, writer: &mut Writer) {
+        use EepromMode::*;
+        (match self {
+            Idle => 0u8,
+            Command => 1,
+            Read => 2,
+            Write => 3,
+            WriteAll => 4,
+        })
+        .save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        use EepromMode::*;
+        let mut value = 0u8;
+        value.load(reader)?;
+        *self = match value {
+            0 => Idle,
+            1 => Command,
+            2 => Read,
+            3 => Write,
+            4 => WriteAll,
+            _ => return Err(Error::InvalidValue),
+        };
+        Ok(())
+    }
+}
+
+snapshot::fields!(Eeprom {
+    chip_select,
+    clock,
+    data_in,
+    data_out,
+    mode,
+    shift,
+    bit_count,
+    address,
+    write_enabled,
+});
+
+snapshot::fields!(Mbc7 {
+    rom_bank_number,
+    ram_enabled_1,
+    ram_enabled_2,
+    is_latch_ready,
+    x_latch,
+    y_latch,
+    eeprom,
+});
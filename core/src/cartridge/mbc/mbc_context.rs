@@ -11,8 +11,12 @@ pub trait MbcContext {
 
     fn ram_mut(&mut self) -> &mut [u8];
 
+    fn rom_bank_count(&self) -> usize {
+        max(1, self.rom().len() / ROM_BANK_SIZE)
+    }
+
     fn rom_bank(&self, bank_number: usize) -> &[u8] {
-        let start = bank_number % max(1, self.rom().len() / ROM_BANK_SIZE) * ROM_BANK_SIZE;
+        let start = bank_number % self.rom_bank_count() * ROM_BANK_SIZE;
         &self.rom()[start..min(self.rom().len(), start + ROM_BANK_SIZE)]
     }
 
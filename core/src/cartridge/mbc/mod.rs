@@ -1,27 +1,60 @@
+mod huc1;
+mod huc3;
 mod mbc1;
 mod mbc2;
 mod mbc3;
 mod mbc5;
+mod mbc7;
 mod mbc_context;
 mod rom_only;
 
+pub use huc1::Huc1;
+pub use huc3::Huc3;
 pub use mbc1::Mbc1;
 pub use mbc2::Mbc2;
 pub use mbc3::Mbc3;
 pub use mbc5::Mbc5;
+pub use mbc7::Mbc7;
 pub use mbc_context::MbcContext;
 pub use rom_only::RomOnly;
 
+use super::rtc::Clock as RtcClock;
+use crate::{snapshot::Snapshot, tilt::Tilt};
 use std::fmt;
 
 pub trait Mbc
 where
-    Self: fmt::Debug,
+    Self: fmt::Debug + Snapshot,
 {
     fn internal_ram_size(&self) -> usize {
         0
     }
 
+    fn mapped_rom_bank(&self, address: u16) -> usize {
+        match address {
+            0x0000..=0x3FFF => 0,
+            _ => 1,
+        }
+    }
+
+    fn tick(&mut self) {}
+
+    fn tone(&self) -> Option<u8> {
+        None
+    }
+
+    fn set_tilt(&mut self, _: Tilt) {}
+
+    fn set_rtc_clock(&mut self, _: RtcClock) {}
+
+    fn rtc_footer(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn load_rtc_footer(&mut self, _: &[u8]) -> bool {
+        false
+    }
+
     fn read_rom(&self, context: &dyn MbcContext, address: u16) -> u8;
 
     fn write_rom(&mut self, context: &mut dyn MbcContext, address: u16, value: u8);
@@ -1,4 +1,5 @@
 use super::{Mbc, MbcContext};
+use crate::snapshot::{Error, Reader, Snapshot, Writer};
 
 #[derive(Debug, Default)]
 pub struct RomOnly {}
@@ -20,3 +21,11 @@ impl Mbc for RomOnly {
         }
     }
 }
+
+impl Snapshot for RomOnly {
+    fn save(&self, _: &mut Writer) {}
+
+    fn load(&mut self, _: &mut Reader) -> Result<(), Error> {
+        Ok(())
+    }
+}
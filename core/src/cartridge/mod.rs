@@ -1,10 +1,21 @@
 pub mod header;
 mod mbc;
+mod rtc;
 
 pub use header::*;
 use mbc::{Mbc, MbcContext};
+pub use rtc::Clock as RtcClock;
 
-use std::{cmp::max, rc::Rc};
+use crate::{
+    cheat::GameGenieCode,
+    snapshot::{self, Reader, Snapshot, Writer},
+    tilt::Tilt,
+};
+
+use std::{
+    cmp::{max, min},
+    rc::Rc,
+};
 
 #[derive(Debug)]
 struct State {
@@ -31,6 +42,7 @@ pub struct Cartridge {
     header: Header,
     state: State,
     mbc: Box<dyn Mbc>,
+    rom_patches: Vec<GameGenieCode>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,15 +57,21 @@ impl From<header::Error> for Error {
     }
 }
 
-pub fn create_mbc(header: &Header) -> Result<Box<dyn Mbc>, Error> {
+pub fn create_mbc(header: &Header, rom: &[u8]) -> Result<Box<dyn Mbc>, Error> {
     use MbcType::*;
     let cartridge_type = &header.cartridge_type;
+    let has_rtc = cartridge_type.options().contains(&CartridgeOption::Timer);
     Ok(match cartridge_type.mbc_type() {
         RomOnly => Box::new(mbc::RomOnly::default()),
+        Mbc1 if mbc::Mbc1::is_multicart(rom) => Box::new(mbc::Mbc1::multicart()),
         Mbc1 => Box::new(mbc::Mbc1::default()),
         Mbc2 => Box::new(mbc::Mbc2::default()),
-        Mbc3 => Box::new(mbc::Mbc3::default()),
+        Mbc3 if mbc::Mbc3::is_mbc30(header) => Box::new(mbc::Mbc3::mbc30(has_rtc)),
+        Mbc3 => Box::new(mbc::Mbc3::new(has_rtc)),
         Mbc5 => Box::new(mbc::Mbc5::default()),
+        Mbc7 => Box::new(mbc::Mbc7::default()),
+        Huc1 => Box::new(mbc::Huc1::default()),
+        Huc3 => Box::new(mbc::Huc3::default()),
         _ => Err(Error::MbcNotImplemented(cartridge_type.clone()))?,
     })
 }
@@ -61,20 +79,97 @@ pub fn create_mbc(header: &Header) -> Result<Box<dyn Mbc>, Error> {
 impl Cartridge {
     pub fn new(rom: Rc<Vec<u8>>) -> Result<Self, Error> {
         let header = Header::load(&rom)?;
-        let mbc = create_mbc(&header)?;
+        let mbc = create_mbc(&header, &rom)?;
         let state = State {
             rom,
             ram: vec![0xFFu8; max(header.ram_size.amount(), mbc.internal_ram_size())],
         };
-        Ok(Self { header, state, mbc })
+        Ok(Self {
+            header,
+            state,
+            mbc,
+            rom_patches: vec![],
+        })
     }
 
     pub fn header(&self) -> &Header {
         &self.header
     }
 
+    pub fn has_battery(&self) -> bool {
+        self.header
+            .cartridge_type
+            .options()
+            .contains(&CartridgeOption::Battery)
+    }
+
+    pub fn ram(&self) -> &[u8] {
+        &self.state.ram
+    }
+
+    pub fn set_rtc_clock(&mut self, clock: RtcClock) {
+        self.mbc.set_rtc_clock(clock);
+    }
+
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        if !self.has_battery() {
+            return None;
+        }
+        let mut data = self.state.ram.clone();
+        if let Some(footer) = self.mbc.rtc_footer() {
+            data.extend(footer);
+        }
+        Some(data)
+    }
+
+    pub fn load_save_data(&mut self, data: &[u8]) {
+        if !self.has_battery() {
+            log::warn!("Attempt to load save data into the cartridge without a battery");
+            return;
+        }
+        let ram_size = self.state.ram.len();
+        let data = if data.len() > ram_size && self.mbc.load_rtc_footer(&data[ram_size..]) {
+            &data[..ram_size]
+        } else {
+            data
+        };
+        if data.len() != ram_size {
+            log::warn!(
+                "The size of the save data does not match: expected {:X}, actual {:X}",
+                ram_size,
+                data.len()
+            );
+        }
+        let length = min(data.len(), ram_size);
+        self.state.ram[..length].copy_from_slice(&data[..length]);
+    }
+
+    pub fn tick(&mut self) {
+        self.mbc.tick();
+    }
+
+    pub fn tone(&self) -> Option<u8> {
+        self.mbc.tone()
+    }
+
+    pub fn set_tilt(&mut self, tilt: Tilt) {
+        self.mbc.set_tilt(tilt);
+    }
+
+    pub fn rom_bank(&self, address: u16) -> usize {
+        self.mbc.mapped_rom_bank(address) % self.state.rom_bank_count()
+    }
+
     pub fn read_rom(&self, address: u16) -> u8 {
-        self.mbc.read_rom(&self.state, address)
+        let value = self.mbc.read_rom(&self.state, address);
+        self.rom_patches
+            .iter()
+            .find_map(|patch| patch.patch(address, value))
+            .unwrap_or(value)
+    }
+
+    pub fn set_rom_patches(&mut self, patches: Vec<GameGenieCode>) {
+        self.rom_patches = patches;
     }
 
     pub fn write_rom(&mut self, address: u16, value: u8) {
@@ -88,4 +183,32 @@ impl Cartridge {
     pub fn write_ram(&mut self, address: u16, value: u8) {
         self.mbc.write_ram(&mut self.state, address, value)
     }
+
+    pub fn write_ram_bank(&mut self, bank_number: usize, address: u16, value: u8) {
+        self.state
+            .write_to_ram_bank(bank_number, address & 0x1FFF, value)
+    }
+}
+
+impl Snapshot for Cartridge {
+    fn save(&self, writer: &mut Writer) {
+        self.header.header_checksum.value().save(writer);
+        self.header.global_checksum.value().save(writer);
+        self.state.ram.save(writer);
+        self.mbc.save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), snapshot::Error> {
+        let mut header_checksum = 0u8;
+        header_checksum.load(reader)?;
+        let mut global_checksum = 0u16;
+        global_checksum.load(reader)?;
+        if header_checksum != self.header.header_checksum.value()
+            || global_checksum != self.header.global_checksum.value()
+        {
+            return Err(snapshot::Error::CartridgeMismatch);
+        }
+        self.state.ram.load(reader)?;
+        self.mbc.load(reader)
+    }
 }
@@ -0,0 +1,271 @@
+use crate::{
+    snapshot::{self, Reader, Snapshot, Writer},
+    util::bits::Bits,
+    M_CYCLES,
+};
+use chrono::Utc;
+use std::{fmt, str::FromStr};
+
+const FOOTER_LENGTH: usize = 48;
+
+const SHORT_FOOTER_LENGTH: usize = 44;
+
+const REGISTERS_LENGTH: usize = 20;
+
+pub fn is_footer(footer: &[u8]) -> bool {
+    [FOOTER_LENGTH, SHORT_FOOTER_LENGTH].contains(&footer.len())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Clock {
+    #[default]
+    Real,
+    Emulated,
+    Fixed(i64),
+}
+
+impl Clock {
+    pub fn now(&self) -> Option<i64> {
+        match self {
+            Self::Real => Some(Utc::now().timestamp()),
+            Self::Emulated => None,
+            Self::Fixed(timestamp) => Some(*timestamp),
+        }
+    }
+}
+
+impl FromStr for Clock {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "real" => Ok(Self::Real),
+            "emulated" => Ok(Self::Emulated),
+            timestamp => timestamp
+                .parse()
+                .map(Self::Fixed)
+                .map_err(|_| format!("Unknown RTC clock: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Real => write!(f, "real"),
+            Self::Emulated => write!(f, "emulated"),
+            Self::Fixed(timestamp) => write!(f, "{}", timestamp),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Registers {
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    days: u16,
+    is_halted: bool,
+    has_day_carry: bool,
+}
+
+impl Registers {
+    fn from_footer(footer: &[u8]) -> Self {
+        let value = |index: usize| footer[index * 4];
+        let mut registers = Self::default();
+        for (select, index) in (0x08..=0x0C).zip(0..) {
+            registers.write(select, value(index));
+        }
+        registers
+    }
+
+    fn footer(&self) -> Vec<u8> {
+        (0x08..=0x0C)
+            .flat_map(|select| (self.read(select) as u32).to_le_bytes())
+            .collect()
+    }
+
+    fn read(&self, select: usize) -> u8 {
+        match select {
+            0x08 => self.seconds,
+            0x09 => self.minutes,
+            0x0A => self.hours,
+            0x0B => (self.days & 0xFF) as u8,
+            0x0C => {
+                (self.days >> 8) as u8
+                    | (self.is_halted as u8) << 6
+                    | (self.has_day_carry as u8) << 7
+            }
+            _ => 0xFF,
+        }
+    }
+
+    fn write(&mut self, select: usize, value: u8) {
+        match select {
+            0x08 => self.seconds = value & 0b0011_1111,
+            0x09 => self.minutes = value & 0b0011_1111,
+            0x0A => self.hours = value & 0b0001_1111,
+            0x0B => self.days = self.days & 0x100 | value as u16,
+            0x0C => {
+                self.days = (value as u16 & 0b1) << 8 | self.days & 0xFF;
+                self.is_halted = value.bit(6);
+                self.has_day_carry = value.bit(7);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_in_range(&self) -> bool {
+        self.seconds < 60 && self.minutes < 60 && self.hours < 24
+    }
+
+    fn advance_second(&mut self) {
+        self.seconds = (self.seconds + 1) & 0b0011_1111;
+        if self.seconds != 60 {
+            return;
+        }
+        self.seconds = 0;
+        self.minutes = (self.minutes + 1) & 0b0011_1111;
+        if self.minutes != 60 {
+            return;
+        }
+        self.minutes = 0;
+        self.hours = (self.hours + 1) & 0b0001_1111;
+        if self.hours != 24 {
+            return;
+        }
+        self.hours = 0;
+        self.days += 1;
+        if self.days == 512 {
+            self.days = 0;
+            self.has_day_carry = true;
+        }
+    }
+
+    fn advance(&mut self, mut seconds: u64) {
+        while seconds > 0 && !self.is_in_range() {
+            self.advance_second();
+            seconds -= 1;
+        }
+        let mut total = seconds
+            + self.seconds as u64
+            + 60 * (self.minutes as u64 + 60 * (self.hours as u64 + 24 * self.days as u64));
+        self.seconds = (total % 60) as u8;
+        total /= 60;
+        self.minutes = (total % 60) as u8;
+        total /= 60;
+        self.hours = (total % 24) as u8;
+        total /= 24;
+        if total >= 512 {
+            self.has_day_carry = true;
+        }
+        self.days = (total % 512) as u16;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rtc {
+    clock: Clock,
+    registers: Registers,
+    latched_registers: Registers,
+    m_cycles: u64,
+    timestamp: i64,
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Rtc {
+    pub fn new(clock: Clock) -> Self {
+        Self {
+            clock,
+            registers: Default::default(),
+            latched_registers: Default::default(),
+            m_cycles: 0,
+            timestamp: clock.now().unwrap_or_default(),
+        }
+    }
+
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+        self.timestamp = clock.now().unwrap_or_default();
+    }
+
+    pub fn tick(&mut self) {
+        self.m_cycles += 1;
+        if self.m_cycles < M_CYCLES {
+            return;
+        }
+        self.m_cycles = 0;
+        self.timestamp += 1;
+        if !self.registers.is_halted {
+            self.registers.advance_second();
+        }
+    }
+
+    pub fn latch(&mut self) {
+        self.latched_registers = self.registers;
+    }
+
+    pub fn read(&self, select: usize) -> u8 {
+        self.latched_registers.read(select)
+    }
+
+    pub fn write(&mut self, select: usize, value: u8) {
+        if select == 0x08 {
+            self.m_cycles = 0;
+        }
+        self.registers.write(select, value);
+        self.latched_registers.write(select, value);
+    }
+
+    pub fn footer(&self) -> Vec<u8> {
+        let mut footer = self.registers.footer();
+        footer.extend(self.latched_registers.footer());
+        footer.extend(self.timestamp.to_le_bytes());
+        footer
+    }
+
+    pub fn load_footer(&mut self, footer: &[u8]) {
+        self.registers = Registers::from_footer(&footer[..REGISTERS_LENGTH]);
+        self.latched_registers =
+            Registers::from_footer(&footer[REGISTERS_LENGTH..REGISTERS_LENGTH * 2]);
+        let timestamp = &footer[REGISTERS_LENGTH * 2..];
+        let saved_timestamp = match timestamp.len() {
+            8 => i64::from_le_bytes(timestamp.try_into().unwrap()),
+            _ => u32::from_le_bytes(timestamp[..4].try_into().unwrap()) as i64,
+        };
+        self.timestamp = self.clock.now().unwrap_or(saved_timestamp);
+        let elapsed = self.timestamp - saved_timestamp;
+        if elapsed > 0 && !self.registers.is_halted {
+            self.registers.advance(elapsed as u64);
+        }
+    }
+}
+
+impl Snapshot for Registers {
+    fn save(&self, writer: &mut Writer) {
+        for select in 0x08..=0x0C {
+            self.read(select).save(writer);
+        }
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), snapshot::Error> {
+        for select in 0x08..=0x0C {
+            let mut value = 0u8;
+            value.load(reader)?;
+            self.write(select, value);
+        }
+        Ok(())
+    }
+}
+
+snapshot::fields!(Rtc {
+    registers,
+    latched_registers,
+    m_cycles,
+    timestamp,
+});
@@ -0,0 +1,119 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameGenieCode {
+    pub address: u16,
+    pub value: u8,
+    pub compare: Option<u8>,
+}
+
+impl GameGenieCode {
+    pub(crate) fn patch(&self, address: u16, value: u8) -> Option<u8> {
+        if address != self.address {
+            return None;
+        }
+        match self.compare {
+            Some(compare) if compare != value => None,
+            _ => Some(self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSharkTarget {
+    Memory,
+    ExternalRam(u8),
+    Wram(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameSharkCode {
+    pub target: GameSharkTarget,
+    pub address: u16,
+    pub value: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheatCode {
+    GameGenie(GameGenieCode),
+    GameShark(GameSharkCode),
+}
+
+impl FromStr for CheatCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_code = || format!("Invalid cheat code: {}", s);
+        let digits = s
+            .chars()
+            .filter(|c| *c != '-')
+            .map(|c| c.to_digit(16).map(|digit| digit as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid_code)?;
+        let byte = |index: usize| digits[index] << 4 | digits[index + 1];
+        match (s.contains('-'), digits.len()) {
+            (true, 6 | 9) => {
+                let address = ((digits[5] ^ 0x0F) as u16) << 12
+                    | (digits[2] as u16) << 8
+                    | (digits[3] as u16) << 4
+                    | digits[4] as u16;
+                if address >= 0x8000 {
+                    return Err(invalid_code());
+                }
+                let compare = (digits.len() == 9)
+                    .then(|| (digits[6] << 4 | digits[8]).rotate_right(2) ^ 0xBA);
+                Ok(Self::GameGenie(GameGenieCode {
+                    address,
+                    value: byte(0),
+                    compare,
+                }))
+            }
+            (false, 8) => {
+                let target = match byte(0) {
+                    0x00 | 0x01 => GameSharkTarget::Memory,
+                    kind @ 0x80..=0x8F => GameSharkTarget::ExternalRam(kind & 0x0F),
+                    kind @ 0x90..=0x97 => GameSharkTarget::Wram(kind & 0x07),
+                    _ => return Err(invalid_code()),
+                };
+                Ok(Self::GameShark(GameSharkCode {
+                    target,
+                    address: (byte(6) as u16) << 8 | byte(4) as u16,
+                    value: byte(2),
+                }))
+            }
+            _ => Err(invalid_code()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cheat {
+    text: String,
+    code: CheatCode,
+    pub is_enabled: bool,
+}
+
+impl Cheat {
+    pub fn code(&self) -> &CheatCode {
+        &self.code
+    }
+}
+
+impl fmt::Display for Cheat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Cheat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_ascii_uppercase();
+        Ok(Self {
+            code: text.parse()?,
+            text,
+            is_enabled: true,
+        })
+    }
+}
@@ -18,6 +18,10 @@ pub trait Components {
     fn enable_interrupts(&mut self);
 
     fn wait(&mut self);
+
+    fn is_static(&self) -> bool;
+
+    fn label(&self, address: u16) -> Option<String>;
 }
 
 fn to_u16(lower: u8, upper: u8) -> u16 {
@@ -154,6 +158,26 @@ impl<'a> Context<'a> {
         self.wait();
     }
 
+    pub fn is_static(&self) -> bool {
+        self.components.is_static()
+    }
+
+    pub fn label(&self, address: u16) -> Option<String> {
+        self.components.label(address)
+    }
+
+    pub fn debug_address(&self, address: u16) -> String {
+        self.label(address)
+            .unwrap_or_else(|| format!("${:04X}", address))
+    }
+
+    pub fn debug_state(&self, state: impl FnOnce(&Self) -> String) -> String {
+        if self.is_static() {
+            return String::new();
+        }
+        format!(" [{}]", state(self))
+    }
+
     pub fn debug_u8(&self, address: u16) -> u8 {
         self.components.read(address)
     }
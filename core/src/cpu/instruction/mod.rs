@@ -6,6 +6,8 @@ mod operator;
 pub use context::Context;
 use operator::Operator;
 
+use crate::{cpu::Registers, symbols::Symbols};
+
 use once_cell::sync::Lazy;
 
 #[derive(Debug)]
@@ -164,22 +166,22 @@ static INSTRUCTIONS: Lazy<Vec<Instruction>> = Lazy::new(|| {
                     0x1F => rra(),
                     // Jumps
                     0xC3 => jp_nn(),
-                    0xC2 => jp_cc(condition::Nz, LITERAL),
-                    0xCA => jp_cc(condition::Z, LITERAL),
-                    0xD2 => jp_cc(condition::Nc, LITERAL),
-                    0xDA => jp_cc(condition::C, LITERAL),
+                    0xC2 => jp_cc(condition::Nz, ADDRESS),
+                    0xCA => jp_cc(condition::Z, ADDRESS),
+                    0xD2 => jp_cc(condition::Nc, ADDRESS),
+                    0xDA => jp_cc(condition::C, ADDRESS),
                     0xE9 => jp_hl(),
-                    0x18 => jr(LITERAL),
-                    0x20 => jr_cc(condition::Nz, LITERAL),
-                    0x28 => jr_cc(condition::Z, LITERAL),
-                    0x30 => jr_cc(condition::Nc, LITERAL),
-                    0x38 => jr_cc(condition::C, LITERAL),
+                    0x18 => jr(RELATIVE_ADDRESS),
+                    0x20 => jr_cc(condition::Nz, RELATIVE_ADDRESS),
+                    0x28 => jr_cc(condition::Z, RELATIVE_ADDRESS),
+                    0x30 => jr_cc(condition::Nc, RELATIVE_ADDRESS),
+                    0x38 => jr_cc(condition::C, RELATIVE_ADDRESS),
                     // Calls
-                    0xCD => call(LITERAL),
-                    0xC4 => call_cc(condition::Nz, LITERAL),
-                    0xCC => call_cc(condition::Z, LITERAL),
-                    0xD4 => call_cc(condition::Nc, LITERAL),
-                    0xDC => call_cc(condition::C, LITERAL),
+                    0xCD => call(ADDRESS),
+                    0xC4 => call_cc(condition::Nz, ADDRESS),
+                    0xCC => call_cc(condition::Z, ADDRESS),
+                    0xD4 => call_cc(condition::Nc, ADDRESS),
+                    0xDC => call_cc(condition::C, ADDRESS),
                     // Restarts
                     0xC7 => rst(0x00),
                     0xCF => rst(0x08),
@@ -206,6 +208,77 @@ static INSTRUCTIONS: Lazy<Vec<Instruction>> = Lazy::new(|| {
         .collect()
 });
 
+struct StaticContextComponents<'a> {
+    registers: Registers,
+    address: u16,
+    bytes: &'a [u8],
+    bank: Option<usize>,
+    symbols: &'a Symbols,
+}
+
+impl<'a> context::Components for StaticContextComponents<'a> {
+    fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    fn read(&self, address: u16) -> u8 {
+        self.bytes
+            .get(address.wrapping_sub(self.address) as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn write(&mut self, _: u16, _: u8) {}
+
+    fn halt(&mut self) {}
+
+    fn stop(&mut self) {}
+
+    fn disable_interrupts(&mut self) {}
+
+    fn enable_interrupts(&mut self) {}
+
+    fn wait(&mut self) {}
+
+    fn is_static(&self) -> bool {
+        true
+    }
+
+    fn label(&self, address: u16) -> Option<String> {
+        let bank = match address {
+            0x0000..=0x3FFF => Some(0),
+            0x4000..=0x7FFF => self.bank.filter(|bank| *bank != 0),
+            _ => None,
+        };
+        self.symbols.describe(bank, address)
+    }
+}
+
+pub fn disassemble(
+    bytes: &[u8],
+    address: u16,
+    bank: Option<usize>,
+    symbols: &Symbols,
+) -> (String, u16) {
+    let mut components = StaticContextComponents {
+        registers: Registers {
+            pc: address,
+            ..Default::default()
+        },
+        address,
+        bytes,
+        bank,
+        symbols,
+    };
+    let mut context = Context::new(&mut components);
+    let instruction = Instruction::fetch(&mut context);
+    (instruction.operator.debug(&context), instruction.length())
+}
+
 impl Instruction {
     pub fn execute(&self, context: &mut Context) {
         self.operator.execute(context);
@@ -222,6 +295,22 @@ impl Instruction {
         }
     }
 
+    pub fn length(&self) -> u16 {
+        match self.sub_opcode {
+            Some(_) => 2,
+            None => match self.opcode {
+                0x06 | 0x0E | 0x16 | 0x1E | 0x26 | 0x2E | 0x36 | 0x3E => 2,
+                0x18 | 0x20 | 0x28 | 0x30 | 0x38 => 2,
+                0xC6 | 0xCE | 0xD6 | 0xDE | 0xE6 | 0xEE | 0xF6 | 0xFE => 2,
+                0xE0 | 0xF0 | 0xE8 | 0xF8 => 2,
+                0x01 | 0x11 | 0x21 | 0x31 | 0x08 | 0xEA | 0xFA => 3,
+                0xC2 | 0xC3 | 0xCA | 0xD2 | 0xDA => 3,
+                0xC4 | 0xCC | 0xCD | 0xD4 | 0xDC => 3,
+                _ => 1,
+            },
+        }
+    }
+
     pub fn debug(&self, context: &Context) -> String {
         format!(
             "({:02X}{}) {}",
@@ -31,11 +31,11 @@ impl Read<bool> for Condition {
 
 impl DebugOperand<bool> for Condition {
     fn debug(&self, context: &Context) -> String {
-        format!(
-            "{}={}",
-            format!("{:?}", self).to_uppercase(),
-            self.is_satisfied(context.flags())
-        )
+        let name = format!("{:?}", self).to_uppercase();
+        if context.is_static() {
+            return name;
+        }
+        format!("{}={}", name, self.is_satisfied(context.flags()))
     }
 }
 
@@ -33,12 +33,36 @@ impl Write<u8> for Indirection {
     }
 }
 
+impl Indirection {
+    fn is_literal(&self) -> bool {
+        self.name.starts_with('$')
+    }
+
+    fn debug_name(&self, context: &Context, address: u16) -> String {
+        self.is_literal()
+            .then(|| context.label(address))
+            .flatten()
+            .unwrap_or_else(|| self.name.into())
+    }
+
+    fn debug_static(&self, context: &Context) -> String {
+        if self.is_literal() {
+            format!("({})", context.debug_address((self.debug_address)(context)))
+        } else {
+            format!("({})", self.name)
+        }
+    }
+}
+
 impl DebugOperand<u8> for Indirection {
     fn debug(&self, context: &Context) -> String {
+        if context.is_static() {
+            return self.debug_static(context);
+        }
         let address = (self.debug_address)(context);
         format!(
             "({}={:04X})={:02X}",
-            self.name,
+            self.debug_name(context, address),
             address,
             context.debug_u8(address)
         )
@@ -61,10 +85,13 @@ impl Write<u16> for Indirection {
 
 impl DebugOperand<u16> for Indirection {
     fn debug(&self, context: &Context) -> String {
+        if context.is_static() {
+            return self.debug_static(context);
+        }
         let address = (self.debug_address)(context);
         format!(
             "({}={:04X})={:04X}",
-            self.name,
+            self.debug_name(context, address),
             address,
             context.debug_u16(address)
         )
@@ -31,3 +31,46 @@ impl DebugOperand<u16> for Literal {
 }
 
 pub const LITERAL: Literal = Literal;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Address;
+
+impl Operand for Address {}
+
+impl Read<u16> for Address {
+    fn read(&self, context: &mut Context) -> u16 {
+        context.fetch16()
+    }
+}
+
+impl DebugOperand<u16> for Address {
+    fn debug(&self, context: &Context) -> String {
+        context.debug_address(context.debug_u16(context.registers().pc))
+    }
+}
+
+pub const ADDRESS: Address = Address;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RelativeAddress;
+
+impl Operand for RelativeAddress {}
+
+impl Read<u8> for RelativeAddress {
+    fn read(&self, context: &mut Context) -> u8 {
+        context.fetch()
+    }
+}
+
+impl DebugOperand<u8> for RelativeAddress {
+    fn debug(&self, context: &Context) -> String {
+        let pc = context.registers().pc;
+        let offset = context.debug_u8(pc);
+        let target = pc.wrapping_add(1).wrapping_add(offset as i8 as u16);
+        context
+            .label(target)
+            .unwrap_or_else(|| format!("${:02X}", offset))
+    }
+}
+
+pub const RELATIVE_ADDRESS: RelativeAddress = RelativeAddress;
@@ -7,7 +7,7 @@ pub mod stack_pointer;
 
 pub use condition::Condition;
 pub use indirection::Indirection;
-pub use literal::LITERAL;
+pub use literal::{ADDRESS, LITERAL, RELATIVE_ADDRESS};
 pub use opcode_register::OpcodeRegister;
 pub use register::{Register, Register16};
 
@@ -55,6 +55,9 @@ impl Write<u8> for Register {
 
 impl DebugOperand<u8> for Register {
     fn debug(&self, context: &Context) -> String {
+        if context.is_static() {
+            return format!("{:?}", self);
+        }
         format!("{:?}={:02X}", self, self.value(context.registers()))
     }
 }
@@ -109,11 +112,11 @@ impl Write<u16> for Register16 {
 
 impl DebugOperand<u16> for Register16 {
     fn debug(&self, context: &Context) -> String {
-        format!(
-            "{}={:04X}",
-            format!("{:?}", self).to_uppercase(),
-            self.value(context.registers())
-        )
+        let name = format!("{:?}", self).to_uppercase();
+        if context.is_static() {
+            return name;
+        }
+        format!("{}={:04X}", name, self.value(context.registers()))
     }
 }
 
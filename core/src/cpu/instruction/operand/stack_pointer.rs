@@ -13,6 +13,9 @@ impl Read<u16> for AddLiteral8 {
 
 impl DebugOperand<u16> for AddLiteral8 {
     fn debug(&self, context: &Context) -> String {
+        if context.is_static() {
+            return format!("SP+${:02X}", context.debug_u8(context.registers().pc));
+        }
         format!(
             "SP={:04X}+#{:02X}",
             context.registers().sp,
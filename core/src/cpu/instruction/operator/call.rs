@@ -9,9 +9,9 @@ pub fn call(address: impl Read<u16>) -> Operator {
         },
         move |context| {
             format!(
-                "CALL {} [PC={:04X}]",
+                "CALL {}{}",
                 address.debug(context),
-                context.registers().pc + 2
+                context.debug_state(|context| format!("PC={:04X}", context.registers().pc + 2))
             )
         },
     )
@@ -27,10 +27,10 @@ pub fn call_cc(condition: Condition, address: impl Read<u16>) -> Operator {
         },
         move |context| {
             format!(
-                "CALL {}, {} [PC={:04X}]",
+                "CALL {}, {}{}",
                 condition.debug(context),
                 address.debug(context),
-                context.registers().pc + 2
+                context.debug_state(|context| format!("PC={:04X}", context.registers().pc + 2))
             )
         },
     )
@@ -48,8 +48,11 @@ pub fn ret() -> Operator {
         |context| context.ret(),
         |context| {
             format!(
-                "RET [(SP)={:04X}]",
-                context.debug_u16(context.registers().sp)
+                "RET{}",
+                context.debug_state(|context| format!(
+                    "(SP)={:04X}",
+                    context.debug_u16(context.registers().sp)
+                ))
             )
         },
     )
@@ -65,9 +68,12 @@ pub fn ret_cc(condition: Condition) -> Operator {
         },
         move |context| {
             format!(
-                "RET {} [(SP)={:04X}]",
+                "RET {}{}",
                 condition.debug(context),
-                context.debug_u16(context.registers().sp),
+                context.debug_state(|context| format!(
+                    "(SP)={:04X}",
+                    context.debug_u16(context.registers().sp)
+                ))
             )
         },
     )
@@ -81,8 +87,11 @@ pub fn reti() -> Operator {
         },
         |context| {
             format!(
-                "RETI [(SP)={:04X}]",
-                context.debug_u16(context.registers().sp),
+                "RETI{}",
+                context.debug_state(|context| format!(
+                    "(SP)={:04X}",
+                    context.debug_u16(context.registers().sp)
+                ))
             )
         },
     )
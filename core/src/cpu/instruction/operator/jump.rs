@@ -14,7 +14,7 @@ pub fn jp_nn() -> Operator {
         |context| {
             format!(
                 "JP {}",
-                DebugOperand::<u16>::debug(&operand::LITERAL, context)
+                DebugOperand::<u16>::debug(&operand::ADDRESS, context)
             )
         },
     )
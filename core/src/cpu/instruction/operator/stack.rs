@@ -20,9 +20,12 @@ pub fn pop(destination: impl Write<u16>) -> Operator {
         },
         move |context| {
             format!(
-                "POP {} [(SP)={:04X}]",
+                "POP {}{}",
                 destination.debug(context),
-                context.debug_u16(context.registers().sp)
+                context.debug_state(|context| format!(
+                    "(SP)={:04X}",
+                    context.debug_u16(context.registers().sp)
+                ))
             )
         },
     )
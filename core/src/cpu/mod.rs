@@ -1,12 +1,15 @@
 pub mod registers;
+pub mod tracer;
 
 mod instruction;
 
-pub use registers::Registers;
+pub use instruction::disassemble;
+pub use registers::{Flags, Registers};
+pub use tracer::{TraceFilter, Tracer};
 
 use instruction::Instruction;
 
-use crate::{interrupt::Interrupt, memory::Memory};
+use crate::{interrupt::Interrupt, memory::Memory, snapshot, symbols::Symbols};
 
 #[derive(Debug, Default)]
 pub struct Cpu {
@@ -16,6 +19,19 @@ pub struct Cpu {
     interrupt_enabled: bool,
     interrupt_enabling: bool,
     wait_m_cycles: u64,
+    symbols: Symbols,
+    tracer: Option<Tracer>,
+}
+
+fn describe_address(symbols: &Symbols, memory: &Memory, address: u16) -> Option<String> {
+    if symbols.is_empty() {
+        return None;
+    }
+    let bank = match address {
+        0x0000..=0x7FFF => Some(memory.components().cartridge.rom_bank(address)),
+        _ => None,
+    };
+    symbols.describe(bank, address)
 }
 
 struct InstructionContextComponents<'a, 'memory> {
@@ -45,6 +61,10 @@ impl<'a, 'memory> instruction::context::Components for InstructionContextCompone
     }
 
     fn stop(&mut self) {
+        if self.memory.components_mut().speed.switch() {
+            self.memory.components_mut().divider.reset();
+            return;
+        }
         self.cpu.is_halted = true;
         self.cpu.is_stopped = true;
     }
@@ -60,9 +80,111 @@ impl<'a, 'memory> instruction::context::Components for InstructionContextCompone
     fn wait(&mut self) {
         self.cpu.wait_m_cycles += 1;
     }
+
+    fn is_static(&self) -> bool {
+        false
+    }
+
+    fn label(&self, address: u16) -> Option<String> {
+        describe_address(&self.cpu.symbols, self.memory, address)
+    }
+}
+
+struct DebugContextComponents<'a, 'memory> {
+    registers: Registers,
+    memory: &'a Memory<'memory>,
+    symbols: &'a Symbols,
+}
+
+impl<'a, 'memory> instruction::context::Components for DebugContextComponents<'a, 'memory> {
+    fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    fn read(&self, address: u16) -> u8 {
+        self.memory.read(address)
+    }
+
+    fn write(&mut self, _: u16, _: u8) {}
+
+    fn halt(&mut self) {}
+
+    fn stop(&mut self) {}
+
+    fn disable_interrupts(&mut self) {}
+
+    fn enable_interrupts(&mut self) {}
+
+    fn wait(&mut self) {}
+
+    fn is_static(&self) -> bool {
+        false
+    }
+
+    fn label(&self, address: u16) -> Option<String> {
+        describe_address(self.symbols, self.memory, address)
+    }
 }
 
 impl Cpu {
+    pub fn new(registers: Registers) -> Self {
+        Self {
+            registers,
+            ..Default::default()
+        }
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn symbols(&self) -> &Symbols {
+        &self.symbols
+    }
+
+    pub fn set_symbols(&mut self, symbols: Symbols) {
+        self.symbols = symbols;
+    }
+
+    pub fn set_tracer(&mut self, tracer: Option<Tracer>) {
+        self.tracer = tracer;
+    }
+
+    pub fn flush_tracer(&mut self) {
+        if let Some(tracer) = &mut self.tracer {
+            if let Err(error) = tracer.flush() {
+                log::warn!("Could not write the trace: {:?}", error);
+                self.tracer = None;
+            }
+        }
+    }
+
+    pub fn stall(&mut self, m_cycles: u64) {
+        self.wait_m_cycles += m_cycles;
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.wait_m_cycles <= 1 && !self.is_halted
+    }
+
+    pub fn debug_instruction(&self, memory: &Memory, address: u16) -> (String, u16) {
+        let mut components = DebugContextComponents {
+            registers: Registers {
+                pc: address,
+                ..self.registers.clone()
+            },
+            memory,
+            symbols: &self.symbols,
+        };
+        let mut context = instruction::Context::new(&mut components);
+        let instruction = Instruction::fetch(&mut context);
+        (instruction.debug(&context), instruction.length())
+    }
+
     fn with_instruction_context<'memory>(
         &mut self,
         memory: &mut Memory<'memory>,
@@ -108,6 +230,12 @@ impl Cpu {
         if self.is_halted {
             return;
         }
+        if let Some(tracer) = &mut self.tracer {
+            if let Err(error) = tracer.trace(&self.registers, memory) {
+                log::warn!("Could not write the trace: {:?}", error);
+                self.tracer = None;
+            }
+        }
         self.with_instruction_context(memory, |instruction_context| {
             let pc = instruction_context.registers().pc;
             let instruction = Instruction::fetch(instruction_context);
@@ -119,3 +247,12 @@ impl Cpu {
         }
     }
 }
+
+snapshot::fields!(Cpu {
+    registers,
+    is_halted,
+    is_stopped,
+    interrupt_enabled,
+    interrupt_enabling,
+    wait_m_cycles,
+});
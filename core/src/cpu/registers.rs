@@ -1,4 +1,5 @@
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Flags {
@@ -36,7 +37,7 @@ impl From<u8> for Flags {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Registers {
     pub a: u8,
     pub f: Flags,
@@ -50,23 +51,6 @@ pub struct Registers {
     pub pc: u16,
 }
 
-impl Default for Registers {
-    fn default() -> Self {
-        Self {
-            a: 0x01,
-            f: Default::default(),
-            b: 0xFF,
-            c: 0x13,
-            d: 0x00,
-            e: 0xC1,
-            h: 0x84,
-            l: 0x03,
-            pc: 0x0100,
-            sp: 0xFFFE,
-        }
-    }
-}
-
 fn to_u16(n: u8, m: u8) -> u16 {
     u16::from_be_bytes([n, m])
 }
@@ -110,3 +94,48 @@ impl Registers {
         [self.h, self.l] = to_bytes(value);
     }
 }
+
+impl fmt::Display for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = |is_set: bool, name: char| if is_set { name } else { '-' };
+        write!(
+            f,
+            "{}{}{}{}",
+            flag(self.z, 'Z'),
+            flag(self.n, 'N'),
+            flag(self.h, 'H'),
+            flag(self.c, 'C')
+        )
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AF={:04X} BC={:04X} DE={:04X} HL={:04X} SP={:04X} PC={:04X} [{}]",
+            self.af(),
+            self.bc(),
+            self.de(),
+            self.hl(),
+            self.sp,
+            self.pc,
+            self.f
+        )
+    }
+}
+
+snapshot::fields!(Flags { z, n, h, c });
+
+snapshot::fields!(Registers {
+    a,
+    f,
+    b,
+    c,
+    d,
+    e,
+    h,
+    l,
+    sp,
+    pc,
+});
@@ -0,0 +1,74 @@
+use crate::{cpu::Registers, memory::Memory};
+use std::{
+    fmt,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TraceFilter {
+    pub pc_range: Option<RangeInclusive<u16>>,
+    pub bank: Option<usize>,
+}
+
+impl TraceFilter {
+    fn matches(&self, pc: u16, memory: &Memory) -> bool {
+        self.pc_range
+            .as_ref()
+            .is_none_or(|pc_range| pc_range.contains(&pc))
+            && self.bank.is_none_or(|bank| {
+                pc <= 0x7FFF && memory.components().cartridge.rom_bank(pc) == bank
+            })
+    }
+}
+
+pub struct Tracer {
+    writer: Box<dyn Write>,
+    filter: TraceFilter,
+}
+
+impl fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracer")
+            .field("filter", &self.filter)
+            .finish()
+    }
+}
+
+impl Tracer {
+    pub fn new(writer: impl Write + 'static, filter: TraceFilter) -> Self {
+        Self {
+            writer: Box::new(BufWriter::new(writer)),
+            filter,
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub(crate) fn trace(&mut self, registers: &Registers, memory: &Memory) -> io::Result<()> {
+        let pc = registers.pc;
+        if !self.filter.matches(pc, memory) {
+            return Ok(());
+        }
+        writeln!(
+            self.writer,
+            "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+            registers.a,
+            registers.af() as u8,
+            registers.b,
+            registers.c,
+            registers.d,
+            registers.e,
+            registers.h,
+            registers.l,
+            registers.sp,
+            pc,
+            memory.peek(pc),
+            memory.peek(pc.wrapping_add(1)),
+            memory.peek(pc.wrapping_add(2)),
+            memory.peek(pc.wrapping_add(3)),
+        )
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{
+    apu::AudioTerminal, game_boy::GameBoy, memory::Access, ppu::Renderer, serial::SerialConnection,
+};
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+const CALL_OPCODES: [u8; 13] = [
+    0xCD, 0xC4, 0xCC, 0xD4, 0xDC, 0xC7, 0xCF, 0xD7, 0xDF, 0xE7, 0xEF, 0xF7, 0xFF,
+];
+
+const RETURN_OPCODES: [u8; 6] = [0xC9, 0xD9, 0xC0, 0xC8, 0xD0, 0xD8];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Breakpoint {
+    pub bank: Option<usize>,
+    pub address: u16,
+}
+
+impl Breakpoint {
+    pub fn new(bank: Option<usize>, address: u16) -> Self {
+        Self { bank, address }
+    }
+
+    fn matches(&self, game_boy: &GameBoy) -> bool {
+        let pc = game_boy.registers().pc;
+        self.address == pc
+            && match (self.bank, game_boy.rom_bank(pc)) {
+                (Some(bank), Some(current_bank)) => bank == current_bank,
+                _ => true,
+            }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bank {
+            Some(bank) => write!(f, "{:02X}:{:04X}", bank, self.address),
+            None => write!(f, "{:04X}", self.address),
+        }
+    }
+}
+
+impl FromStr for Breakpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_hex = |value: &str| {
+            usize::from_str_radix(value.trim_start_matches('$'), 16)
+                .map_err(|_| format!("Invalid breakpoint: {}", s))
+        };
+        let (bank, address) = match s.split_once(':') {
+            Some((bank, address)) => (Some(parse_hex(bank)?), parse_hex(address)?),
+            None => (None, parse_hex(s)?),
+        };
+        let address = u16::try_from(address).map_err(|_| format!("Invalid breakpoint: {}", s))?;
+        Ok(Self { bank, address })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    Continue,
+    StepInto,
+    StepOver,
+    StepOut,
+    RunTo(Breakpoint),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    Breakpoint(Breakpoint),
+    Watchpoint { access: Access, pc: u16 },
+    Stepped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Target {
+    Continue,
+    NextInstruction,
+    Return { address: u16, sp: u16 },
+    Out { sp: u16 },
+    Cursor(Breakpoint),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Debugger {
+    breakpoints: BTreeSet<Breakpoint>,
+    target: Option<Target>,
+    last_pc: u16,
+    last_opcode: Option<u8>,
+}
+
+impl Debugger {
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) -> bool {
+        self.breakpoints.remove(breakpoint)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.target.is_none()
+    }
+
+    pub fn pause(&mut self) {
+        self.target = None;
+    }
+
+    pub fn resume(&mut self, game_boy: &mut GameBoy, command: Command) {
+        let registers = game_boy.registers().clone();
+        let opcode = game_boy.read(registers.pc);
+        game_boy.watcher_mut().take_hit();
+        self.last_pc = registers.pc;
+        self.last_opcode = Some(opcode);
+        self.target = Some(match command {
+            Command::Continue => Target::Continue,
+            Command::StepInto => Target::NextInstruction,
+            Command::StepOver if CALL_OPCODES.contains(&opcode) => Target::Return {
+                address: game_boy.disassemble(registers.pc).bytes.len() as u16 + registers.pc,
+                sp: registers.sp,
+            },
+            Command::StepOver => Target::NextInstruction,
+            Command::StepOut => Target::Out { sp: registers.sp },
+            Command::RunTo(breakpoint) => Target::Cursor(breakpoint),
+        });
+    }
+
+    fn reaches_target(&self, game_boy: &GameBoy) -> bool {
+        let registers = game_boy.registers();
+        match self.target {
+            Some(Target::NextInstruction) => true,
+            Some(Target::Return { address, sp }) => registers.pc == address && registers.sp >= sp,
+            Some(Target::Out { sp }) => {
+                registers.sp > sp
+                    && self
+                        .last_opcode
+                        .is_some_and(|opcode| RETURN_OPCODES.contains(&opcode))
+            }
+            Some(Target::Cursor(breakpoint)) => breakpoint.matches(game_boy),
+            Some(Target::Continue) | None => false,
+        }
+    }
+
+    pub fn tick(
+        &mut self,
+        game_boy: &mut GameBoy,
+        renderer: &mut impl Renderer,
+        audio_terminal: &mut impl AudioTerminal,
+        serial_connection: &mut impl SerialConnection,
+    ) -> Option<Event> {
+        self.target?;
+        loop {
+            let is_tick_done = game_boy.tick_cpu(renderer, audio_terminal, serial_connection);
+            if game_boy.is_ready_for_instruction() {
+                if let Some(event) = self.check_instruction(game_boy) {
+                    return Some(event);
+                }
+            }
+            if is_tick_done {
+                return None;
+            }
+        }
+    }
+
+    fn check_instruction(&mut self, game_boy: &mut GameBoy) -> Option<Event> {
+        let hit = game_boy.watcher_mut().take_hit();
+        let event = if let Some(&breakpoint) = self
+            .breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.matches(game_boy))
+        {
+            Some(Event::Breakpoint(breakpoint))
+        } else if let Some(access) = hit {
+            Some(Event::Watchpoint {
+                access,
+                pc: self.last_pc,
+            })
+        } else if self.reaches_target(game_boy) {
+            Some(Event::Stepped)
+        } else {
+            None
+        };
+        self.last_pc = game_boy.registers().pc;
+        self.last_opcode = Some(game_boy.read(self.last_pc));
+        if event.is_some() {
+            self.pause();
+        }
+        event
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{cpu, symbols::Symbols};
+use std::ops::RangeInclusive;
+
+const BANK_SIZE: usize = 0x4000;
+
+const HEADER_FIELDS: [(RangeInclusive<u16>, &str); 14] = [
+    (0x0100..=0x0103, "Entry point"),
+    (0x0104..=0x0133, "Nintendo logo"),
+    (0x0134..=0x0142, "Title"),
+    (0x0143..=0x0143, "CGB flag"),
+    (0x0144..=0x0145, "New licensee code"),
+    (0x0146..=0x0146, "SGB flag"),
+    (0x0147..=0x0147, "Cartridge type"),
+    (0x0148..=0x0148, "ROM size"),
+    (0x0149..=0x0149, "RAM size"),
+    (0x014A..=0x014A, "Destination code"),
+    (0x014B..=0x014B, "Old licensee code"),
+    (0x014C..=0x014C, "Mask ROM version"),
+    (0x014D..=0x014D, "Header checksum"),
+    (0x014E..=0x014F, "Global checksum"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    BankOutOfRange(usize),
+    AddressOutOfBank(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Disassembly {
+    pub bank: Option<usize>,
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub text: String,
+    pub comment: Option<String>,
+}
+
+fn decode(bytes: &[u8], address: u16, bank: Option<usize>, symbols: &Symbols) -> Disassembly {
+    let (text, length) = cpu::disassemble(bytes, address, bank, symbols);
+    Disassembly {
+        bank,
+        address,
+        bytes: (0..length as usize)
+            .map(|offset| bytes.get(offset).copied().unwrap_or_default())
+            .collect(),
+        text,
+        comment: None,
+    }
+}
+
+pub fn disassemble(bytes: &[u8], address: u16) -> Disassembly {
+    decode(bytes, address, None, &Default::default())
+}
+
+pub fn register_name(address: u16) -> Option<&'static str> {
+    Some(match address {
+        0xFF00 => "P1",
+        0xFF01 => "SB",
+        0xFF02 => "SC",
+        0xFF04 => "DIV",
+        0xFF05 => "TIMA",
+        0xFF06 => "TMA",
+        0xFF07 => "TAC",
+        0xFF0F => "IF",
+        0xFF10 => "NR10",
+        0xFF11 => "NR11",
+        0xFF12 => "NR12",
+        0xFF13 => "NR13",
+        0xFF14 => "NR14",
+        0xFF16 => "NR21",
+        0xFF17 => "NR22",
+        0xFF18 => "NR23",
+        0xFF19 => "NR24",
+        0xFF1A => "NR30",
+        0xFF1B => "NR31",
+        0xFF1C => "NR32",
+        0xFF1D => "NR33",
+        0xFF1E => "NR34",
+        0xFF20 => "NR41",
+        0xFF21 => "NR42",
+        0xFF22 => "NR43",
+        0xFF23 => "NR44",
+        0xFF24 => "NR50",
+        0xFF25 => "NR51",
+        0xFF26 => "NR52",
+        0xFF30..=0xFF3F => "WAVE",
+        0xFF40 => "LCDC",
+        0xFF41 => "STAT",
+        0xFF42 => "SCY",
+        0xFF43 => "SCX",
+        0xFF44 => "LY",
+        0xFF45 => "LYC",
+        0xFF46 => "DMA",
+        0xFF47 => "BGP",
+        0xFF48 => "OBP0",
+        0xFF49 => "OBP1",
+        0xFF4A => "WY",
+        0xFF4B => "WX",
+        0xFF4D => "KEY1",
+        0xFF4F => "VBK",
+        0xFF50 => "BOOT",
+        0xFF51 => "HDMA1",
+        0xFF52 => "HDMA2",
+        0xFF53 => "HDMA3",
+        0xFF54 => "HDMA4",
+        0xFF55 => "HDMA5",
+        0xFF56 => "RP",
+        0xFF68 => "BCPS",
+        0xFF69 => "BCPD",
+        0xFF6A => "OCPS",
+        0xFF6B => "OCPD",
+        0xFF6C => "OPRI",
+        0xFF70 => "SVBK",
+        0xFF76 => "PCM12",
+        0xFF77 => "PCM34",
+        0xFFFF => "IE",
+        _ => return None,
+    })
+}
+
+fn header_field(address: u16) -> Option<&'static (RangeInclusive<u16>, &'static str)> {
+    HEADER_FIELDS
+        .iter()
+        .find(|(range, _)| range.contains(&address))
+}
+
+fn referenced_address(disassembly: &Disassembly) -> Option<u16> {
+    let bytes = &disassembly.bytes;
+    let u16_operand = || u16::from_le_bytes([bytes[1], bytes[2]]);
+    match bytes[0] {
+        0xE0 | 0xF0 => Some(0xFF00 | bytes[1] as u16),
+        0x01 | 0x11 | 0x21 | 0x31 | 0x08 | 0xEA | 0xFA => Some(u16_operand()),
+        _ => None,
+    }
+}
+
+fn annotate(disassembly: &Disassembly) -> Option<String> {
+    let bytes = &disassembly.bytes;
+    match bytes[0] {
+        0x18 | 0x20 | 0x28 | 0x30 | 0x38 => {
+            let target = disassembly
+                .address
+                .wrapping_add(2)
+                .wrapping_add(bytes[1] as i8 as u16);
+            return Some(format!("to ${:04X}", target));
+        }
+        _ => {}
+    }
+    let address = referenced_address(disassembly)?;
+    register_name(address)
+        .or_else(|| header_field(address).map(|(_, name)| *name))
+        .map(Into::into)
+}
+
+#[derive(Debug, Clone)]
+pub struct Disassembler<'a> {
+    rom: &'a [u8],
+    symbols: Option<&'a Symbols>,
+    annotates: bool,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(rom: &'a [u8]) -> Self {
+        Self {
+            rom,
+            symbols: None,
+            annotates: false,
+        }
+    }
+
+    pub fn symbols(mut self, symbols: &'a Symbols) -> Self {
+        self.symbols = Some(symbols);
+        self
+    }
+
+    pub fn annotates(mut self, annotates: bool) -> Self {
+        self.annotates = annotates;
+        self
+    }
+
+    pub fn bank_count(&self) -> usize {
+        self.rom.len().div_ceil(BANK_SIZE)
+    }
+
+    pub fn bank_addresses(bank: usize) -> RangeInclusive<u16> {
+        match bank {
+            0 => 0x0000..=0x3FFF,
+            _ => 0x4000..=0x7FFF,
+        }
+    }
+
+    fn bank_bytes(&self, bank: usize, address: u16) -> Result<&'a [u8], Error> {
+        if bank >= self.bank_count() {
+            return Err(Error::BankOutOfRange(bank));
+        }
+        let addresses = Self::bank_addresses(bank);
+        if !addresses.contains(&address) {
+            return Err(Error::AddressOutOfBank(address));
+        }
+        let start = bank * BANK_SIZE + (address - addresses.start()) as usize;
+        let end = std::cmp::min((bank + 1) * BANK_SIZE, self.rom.len());
+        Ok(&self.rom[start..end])
+    }
+
+    fn header_data(&self, bytes: &[u8], address: u16) -> Option<Disassembly> {
+        let (range, name) = header_field(address)?;
+        if *range.start() < 0x0104 {
+            return None;
+        }
+        let length = std::cmp::min(range.end() - address + 1, 16) as usize;
+        let bytes = bytes[..std::cmp::min(length, bytes.len())].to_vec();
+        Some(Disassembly {
+            bank: Some(0),
+            address,
+            text: format!(
+                "DB {}",
+                bytes
+                    .iter()
+                    .map(|byte| format!("${:02X}", byte))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            bytes,
+            comment: Some((*name).into()),
+        })
+    }
+
+    pub fn disassemble(&self, bank: usize, address: u16) -> Result<Disassembly, Error> {
+        let bytes = self.bank_bytes(bank, address)?;
+        if self.annotates && bank == 0 {
+            if let Some(disassembly) = self.header_data(bytes, address) {
+                return Ok(disassembly);
+            }
+        }
+        let empty_symbols = Symbols::default();
+        let symbols = self.symbols.unwrap_or(&empty_symbols);
+        let mut disassembly = decode(bytes, address, Some(bank), symbols);
+        if self.annotates {
+            disassembly.comment = match (bank, header_field(address)) {
+                (0, Some((range, name))) if *range.start() == address => Some((*name).into()),
+                _ => annotate(&disassembly),
+            };
+        }
+        Ok(disassembly)
+    }
+
+    pub fn disassemble_bank(&self, bank: usize, from: u16) -> Result<Vec<Disassembly>, Error> {
+        let length = self.bank_bytes(bank, from)?.len();
+        let mut result = Vec::new();
+        let mut offset = 0;
+        while offset < length {
+            let disassembly = self.disassemble(bank, from + offset as u16)?;
+            offset += disassembly.bytes.len();
+            result.push(disassembly);
+        }
+        Ok(result)
+    }
+}
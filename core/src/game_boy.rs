@@ -1,17 +1,32 @@
 use crate::{
     apu::{Apu, AudioTerminal},
-    cartridge::Cartridge,
-    cpu::Cpu,
+    boot_rom::{BootRom, BootRomOverlay},
+    cartridge::{Cartridge, CgbSupport},
+    cheat::{Cheat, CheatCode, GameSharkTarget},
+    cpu::{Cpu, Registers, Tracer},
+    disassembler::Disassembly,
     interrupt::InterruptController,
     joypad::{ButtonState, Joypad},
-    memory::{self, Dma, Hram, Memory, Wram},
+    memory::{self, Dma, Hdma, Hram, Memory, Watcher, Wram},
+    model::Model,
     ppu::{Ppu, Renderer},
-    serial::{Serial, SerialConnection},
+    serial::{BitExchange, Serial, SerialConnection},
+    snapshot::{self, Reader, Snapshot, Writer},
+    speed::Speed,
+    symbols::Symbols,
+    tilt::Tilt,
     timer::{Divider, Timer},
 };
+use std::cell::RefCell;
+
+const SAVE_STATE_MAGIC: &[u8] = b"UUGBSTATE";
+
+const SAVE_STATE_VERSION: u32 = 2;
 
 #[derive(Debug)]
 pub struct GameBoy {
+    model: Model,
+    boot_rom_overlay: BootRomOverlay,
     cartridge: Cartridge,
     cpu: Cpu,
     wram: Wram,
@@ -24,30 +39,249 @@ pub struct GameBoy {
     timer: Timer,
     serial: Serial,
     dma: Dma,
+    hdma: Hdma,
+    speed: Speed,
+    rest_m_cycles: u64,
+    watcher: RefCell<Watcher>,
+    cheats: Vec<Cheat>,
 }
 
-impl GameBoy {
-    pub fn new(cartridge: Cartridge) -> Self {
-        Self {
-            cartridge,
-            cpu: Default::default(),
-            wram: Default::default(),
-            ppu: Default::default(),
-            apu: Default::default(),
-            hram: Default::default(),
-            interrupt_controller: Default::default(),
+#[derive(Debug)]
+pub struct GameBoyBuilder {
+    cartridge: Cartridge,
+    model: Option<Model>,
+    boot_rom: Option<BootRom>,
+}
+
+impl GameBoyBuilder {
+    pub fn model(mut self, model: Model) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn boot_rom(mut self, boot_rom: BootRom) -> Self {
+        self.boot_rom = Some(boot_rom);
+        self
+    }
+
+    pub fn build(self) -> GameBoy {
+        let is_cgb_supported = match self.cartridge.header().cgb_flag.support() {
+            CgbSupport::Supported | CgbSupport::Only => true,
+            CgbSupport::None => false,
+        };
+        let model = self.model.unwrap_or(match &self.boot_rom {
+            Some(boot_rom) if boot_rom.is_cgb() => Model::Cgb,
+            Some(_) => Model::Dmg,
+            None if is_cgb_supported => Model::Cgb,
+            None => Model::Dmg,
+        });
+        let is_booting = self.boot_rom.is_some();
+        let boots_cgb = self.boot_rom.as_ref().is_some_and(BootRom::is_cgb);
+        let is_cgb_mode = model.is_cgb() && (is_cgb_supported || boots_cgb);
+        GameBoy {
+            model,
+            boot_rom_overlay: BootRomOverlay::new(self.boot_rom),
+            cpu: Cpu::new(if is_booting {
+                Default::default()
+            } else {
+                model.registers(self.cartridge.header(), is_cgb_mode)
+            }),
+            cartridge: self.cartridge,
+            wram: Wram::new(is_cgb_mode, |index| model.wram_fill(index)),
+            ppu: if is_booting {
+                Ppu::power_on(is_cgb_mode)
+            } else {
+                Ppu::new(is_cgb_mode, model.current_line())
+            },
+            apu: if is_booting {
+                Apu::power_on()
+            } else {
+                Apu::new(model.plays_boot_sound())
+            },
+            hram: Hram::new(|index| model.hram_fill(index)),
+            interrupt_controller: if is_booting {
+                InterruptController::power_on()
+            } else {
+                Default::default()
+            },
             joypad: Default::default(),
-            divider: Default::default(),
+            divider: Divider::new(if is_booting {
+                0x0000
+            } else {
+                model.divider_counter(is_cgb_mode)
+            }),
             timer: Default::default(),
-            serial: Default::default(),
+            serial: Serial::new(is_cgb_mode),
             dma: Default::default(),
+            hdma: Hdma::new(is_cgb_mode),
+            speed: Speed::new(is_cgb_mode),
+            rest_m_cycles: 0,
+            watcher: Default::default(),
+            cheats: vec![],
+        }
+    }
+}
+
+impl GameBoy {
+    pub fn new(cartridge: Cartridge) -> Self {
+        Self::builder(cartridge).build()
+    }
+
+    pub fn builder(cartridge: Cartridge) -> GameBoyBuilder {
+        GameBoyBuilder {
+            cartridge,
+            model: None,
+            boot_rom: None,
+        }
+    }
+
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    pub fn cartridge(&self) -> &Cartridge {
+        &self.cartridge
+    }
+
+    pub fn cartridge_mut(&mut self) -> &mut Cartridge {
+        &mut self.cartridge
+    }
+
+    pub fn registers(&self) -> &Registers {
+        self.cpu.registers()
+    }
+
+    pub fn symbols(&self) -> &Symbols {
+        self.cpu.symbols()
+    }
+
+    pub fn set_symbols(&mut self, symbols: Symbols) {
+        self.cpu.set_symbols(symbols);
+    }
+
+    pub fn set_tracer(&mut self, tracer: Option<Tracer>) {
+        self.cpu.set_tracer(tracer);
+    }
+
+    pub fn flush_tracer(&mut self) {
+        self.cpu.flush_tracer();
+    }
+
+    pub fn is_ready_for_instruction(&self) -> bool {
+        self.cpu.is_ready()
+    }
+
+    pub fn rom_bank(&self, address: u16) -> Option<usize> {
+        match address {
+            0x0000..=0x7FFF => Some(self.cartridge.rom_bank(address)),
+            _ => None,
+        }
+    }
+
+    pub fn read(&mut self, address: u16) -> u8 {
+        let (_, memory) = self.separate_components();
+        memory.read(address)
+    }
+
+    pub fn disassemble(&mut self, address: u16) -> Disassembly {
+        let bank = self.rom_bank(address);
+        let (cpu, memory) = self.separate_components();
+        let (text, length) = cpu.debug_instruction(&memory, address);
+        Disassembly {
+            bank,
+            address,
+            bytes: (0..length)
+                .map(|offset| memory.read(address.wrapping_add(offset)))
+                .collect(),
+            text,
+            comment: None,
+        }
+    }
+
+    pub fn watcher_mut(&mut self) -> &mut Watcher {
+        self.watcher.get_mut()
+    }
+
+    pub fn cheats(&self) -> &[Cheat] {
+        &self.cheats
+    }
+
+    pub fn add_cheat(&mut self, cheat: Cheat) {
+        self.cheats.push(cheat);
+        self.update_rom_patches();
+    }
+
+    pub fn remove_cheat(&mut self, index: usize) -> Option<Cheat> {
+        if index >= self.cheats.len() {
+            return None;
+        }
+        let cheat = self.cheats.remove(index);
+        self.update_rom_patches();
+        Some(cheat)
+    }
+
+    pub fn set_cheat_enabled(&mut self, index: usize, is_enabled: bool) {
+        if let Some(cheat) = self.cheats.get_mut(index) {
+            cheat.is_enabled = is_enabled;
+            self.update_rom_patches();
+        }
+    }
+
+    fn update_rom_patches(&mut self) {
+        let patches = self
+            .cheats
+            .iter()
+            .filter(|cheat| cheat.is_enabled)
+            .filter_map(|cheat| match cheat.code() {
+                CheatCode::GameGenie(code) => Some(*code),
+                CheatCode::GameShark(_) => None,
+            })
+            .collect();
+        self.cartridge.set_rom_patches(patches);
+    }
+
+    fn apply_ram_cheats(&mut self) {
+        let codes: Vec<_> = self
+            .cheats
+            .iter()
+            .filter(|cheat| cheat.is_enabled)
+            .filter_map(|cheat| match cheat.code() {
+                CheatCode::GameShark(code) => Some(*code),
+                CheatCode::GameGenie(_) => None,
+            })
+            .collect();
+        for code in codes {
+            match (code.target, code.address) {
+                (GameSharkTarget::ExternalRam(bank), 0xA000..=0xBFFF) => self
+                    .cartridge
+                    .write_ram_bank(bank as usize, code.address, code.value),
+                (GameSharkTarget::Wram(bank), 0xD000..=0xDFFF) => {
+                    self.wram
+                        .write_to_bank(bank, code.address - 0xD000, code.value)
+                }
+                _ => {
+                    let (_, mut memory) = self.separate_components();
+                    memory.write(code.address, code.value);
+                }
+            }
         }
     }
 
     fn separate_components(&mut self) -> (&mut Cpu, Memory) {
+        let (cpu, components, _) = self.split_components();
+        (cpu, Memory::new(components))
+    }
+
+    fn separate_watched_components(&mut self) -> (&mut Cpu, Memory<'_>) {
+        let (cpu, components, watcher) = self.split_components();
+        (cpu, Memory::watched(components, watcher))
+    }
+
+    fn split_components(&mut self) -> (&mut Cpu, memory::Components<'_>, &RefCell<Watcher>) {
         (
             &mut self.cpu,
-            Memory::new(memory::Components {
+            memory::Components {
+                boot_rom_overlay: &mut self.boot_rom_overlay,
                 cartridge: &mut self.cartridge,
                 wram: &mut self.wram,
                 ppu: &mut self.ppu,
@@ -59,7 +293,10 @@ impl GameBoy {
                 timer: &mut self.timer,
                 serial: &mut self.serial,
                 dma: &mut self.dma,
-            }),
+                hdma: &mut self.hdma,
+                speed: &mut self.speed,
+            },
+            &self.watcher,
         )
     }
 
@@ -69,21 +306,62 @@ impl GameBoy {
         autio_terminal: &mut impl AudioTerminal,
         serial_connection: &mut impl SerialConnection,
     ) {
+        while !self.tick_cpu(renderer, autio_terminal, serial_connection) {}
+    }
+
+    pub fn tick_cpu(
+        &mut self,
+        renderer: &mut impl Renderer,
+        autio_terminal: &mut impl AudioTerminal,
+        serial_connection: &mut impl SerialConnection,
+    ) -> bool {
+        if self.rest_m_cycles == 0 {
+            self.tick_peripherals(renderer, autio_terminal);
+            self.rest_m_cycles = self.speed.m_cycles_per_tick();
+        }
+        self.tick_m_cycle(serial_connection);
+        self.rest_m_cycles -= 1;
+        self.rest_m_cycles == 0
+    }
+
+    fn tick_peripherals(
+        &mut self,
+        renderer: &mut impl Renderer,
+        autio_terminal: &mut impl AudioTerminal,
+    ) {
+        let was_in_vblank = self.ppu.is_in_vblank();
         for _ in 0..4 {
-            self.divider.tick();
             self.ppu.tick(&mut self.interrupt_controller, renderer);
         }
+        if !was_in_vblank && self.ppu.is_in_vblank() && !self.cheats.is_empty() {
+            self.apply_ram_cheats();
+        }
+        self.apu.set_tone(self.cartridge.tone());
         for _ in 0..2 {
             self.apu.tick(autio_terminal);
         }
-        let (cpu, mut memory) = self.separate_components();
+        self.cartridge.tick();
+    }
+
+    fn tick_m_cycle(&mut self, serial_connection: &mut impl SerialConnection) {
+        for _ in 0..4 {
+            self.divider.tick();
+        }
+        let (cpu, mut memory) = self.separate_watched_components();
         cpu.tick(&mut memory);
         self.timer
             .tick(&self.divider, &mut self.interrupt_controller);
         self.serial
             .tick(&mut self.interrupt_controller, serial_connection);
-        let (_, mut memory) = self.separate_components();
-        memory.tick();
+        let (cpu, mut memory) = self.separate_components();
+        let stall_m_cycles = memory.tick();
+        cpu.stall(stall_m_cycles);
+    }
+
+    pub fn exchange_serial_bit(&mut self, bit: bool) -> bool {
+        let mut exchange = BitExchange::new(bit);
+        self.serial.receive(&mut exchange);
+        exchange.output()
     }
 
     pub fn set_button_state(&mut self, button_state: ButtonState) {
@@ -91,6 +369,10 @@ impl GameBoy {
             .set_button_state(button_state, &mut self.interrupt_controller);
     }
 
+    pub fn set_tilt(&mut self, tilt: Tilt) {
+        self.cartridge.set_tilt(tilt);
+    }
+
     pub fn dump(&mut self) -> Vec<u8> {
         let (_, memory) = self.separate_components();
         let mut buffer = vec![];
@@ -99,4 +381,69 @@ impl GameBoy {
         }
         buffer
     }
+
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.write(SAVE_STATE_MAGIC);
+        SAVE_STATE_VERSION.save(&mut writer);
+        self.model.save(&mut writer);
+        self.save(&mut writer);
+        writer.into_bytes()
+    }
+
+    fn load_state_unchecked(&mut self, data: &[u8]) -> Result<(), snapshot::Error> {
+        let mut reader = Reader::new(data);
+        if reader.read(SAVE_STATE_MAGIC.len())? != SAVE_STATE_MAGIC {
+            return Err(snapshot::Error::InvalidMagic);
+        }
+        let mut version = 0u32;
+        version.load(&mut reader)?;
+        if version != SAVE_STATE_VERSION {
+            return Err(snapshot::Error::UnsupportedVersion(version));
+        }
+        let mut model = self.model;
+        model.load(&mut reader)?;
+        if model != self.model {
+            return Err(snapshot::Error::ModelMismatch);
+        }
+        self.load(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(snapshot::Error::InvalidValue);
+        }
+        if self.boot_rom_overlay.is_cgb() {
+            let is_compatible = self.boot_rom_overlay.is_dmg_compatible();
+            let (_, mut memory) = self.separate_components();
+            memory
+                .components_mut()
+                .set_dmg_compatibility_mode(is_compatible);
+        }
+        Ok(())
+    }
+
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), snapshot::Error> {
+        let backup = self.save_state();
+        self.load_state_unchecked(data).inspect_err(|_| {
+            self.load_state_unchecked(&backup)
+                .expect("Could not restore the state from the backup");
+        })
+    }
 }
+
+snapshot::fields!(GameBoy {
+    boot_rom_overlay,
+    cartridge,
+    cpu,
+    wram,
+    ppu,
+    apu,
+    hram,
+    interrupt_controller,
+    joypad,
+    divider,
+    timer,
+    serial,
+    dma,
+    hdma,
+    speed,
+    rest_m_cycles,
+});
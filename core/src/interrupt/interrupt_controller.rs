@@ -1,5 +1,5 @@
 use super::Interrupt;
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct State {
@@ -20,9 +20,7 @@ pub struct InterruptController {
 
 impl Default for InterruptController {
     fn default() -> Self {
-        let mut result = Self {
-            states: Default::default(),
-        };
+        let mut result = Self::power_on();
         result.request(Interrupt::VBlank);
         result
     }
@@ -35,6 +33,12 @@ impl Interrupt {
 }
 
 impl InterruptController {
+    pub fn power_on() -> Self {
+        Self {
+            states: Default::default(),
+        }
+    }
+
     fn state(&self, interrupt: Interrupt) -> &State {
         &self.states[interrupt.bit() as usize]
     }
@@ -95,3 +99,10 @@ impl InterruptController {
         self.set_bits(|state, bit| state.is_requested = bit, bits);
     }
 }
+
+snapshot::fields!(State {
+    is_enabled,
+    is_requested,
+});
+
+snapshot::fields!(InterruptController { states });
@@ -1,5 +1,6 @@
 use crate::{
     interrupt::{Interrupt, InterruptController},
+    snapshot,
     util::bits::Bits,
 };
 
@@ -88,3 +89,20 @@ impl Joypad {
         });
     }
 }
+
+snapshot::fields!(ButtonState {
+    up,
+    down,
+    left,
+    right,
+    a,
+    b,
+    start,
+    select,
+});
+
+snapshot::fields!(Joypad {
+    button_state,
+    action_is_selected,
+    direction_is_selected,
+});
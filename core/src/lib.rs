@@ -1,26 +1,65 @@
 mod apu;
+mod boot_rom;
 mod cartridge;
+mod cheat;
 mod cpu;
+mod debugger;
+mod disassembler;
 mod game_boy;
 mod interrupt;
 mod joypad;
 mod memory;
+mod model;
+mod patch;
 mod ppu;
+mod ram_search;
 mod serial;
+mod snapshot;
+mod speed;
+mod symbols;
+mod tilt;
 mod timer;
 
 mod util;
 
 pub use apu::{
-    AudioFrame, AudioTerminal, MAX_FRAME_VOLUME as MAX_AUDIO_FRAME_VOLUME,
+    AudioFrame, AudioTerminal, NoAudioTerminal, MAX_FRAME_VOLUME as MAX_AUDIO_FRAME_VOLUME,
     SAMPLE_RATE as AUDIO_SAMPLE_RATE,
 };
-pub use cartridge::{Cartridge, Header};
-pub use game_boy::GameBoy;
+pub use boot_rom::{BootRom, Error as BootRomError};
+pub use cartridge::{Cartridge, Error as CartridgeError, Header, RtcClock, NINTENDO_LOGO};
+pub use cheat::{Cheat, CheatCode, GameGenieCode, GameSharkCode, GameSharkTarget};
+pub use cpu::{Flags, Registers, TraceFilter, Tracer};
+pub use debugger::{Breakpoint, Command as DebuggerCommand, Debugger, Event as DebuggerEvent};
+pub use disassembler::{
+    disassemble, register_name, Disassembler, Disassembly, Error as DisassemblerError,
+};
+pub use game_boy::{GameBoy, GameBoyBuilder};
 pub use joypad::ButtonState;
-pub use ppu::{display_size, Color, Renderer, Vec2};
-pub use serial::{BufferedSerialConnection, NoSerialConnection, SerialConnection};
+pub use memory::{Access, AccessKind, Watcher, Watchpoint};
+pub use model::Model;
+pub use patch::{
+    apply as apply_patch, find as find_patch, Error as PatchError, EXTENSIONS as PATCH_EXTENSIONS,
+};
+pub use ppu::{display_size, Color, NoRenderer, Renderer, Rgb, Vec2};
+pub use ram_search::{
+    Filter as RamSearchFilter, RamAddress, RamSearch, SearchResult as RamSearchResult,
+    ValueType as RamSearchValueType,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use serial::SocketConnection;
+pub use serial::{
+    BufferedSerialConnection, FourPlayerAdapter, LinkPort, NoSerialConnection, PrintedImage,
+    Printer, ReceivedByte as ReceivedSerialByte, SerialConnection,
+    MAX_PLAYERS as MAX_ADAPTER_PLAYERS,
+};
+pub use snapshot::Error as SaveStateError;
+pub use symbols::{Error as SymbolsError, Symbols};
+pub use tilt::{Tilt, TiltScript, TiltSource};
+pub use util::crc32::crc32;
 
 pub const CLOCK_CYCLE: u64 = 4194304;
 
 pub const M_CYCLES: u64 = CLOCK_CYCLE / 4;
+
+pub const M_CYCLES_PER_FRAME: u64 = 17556;
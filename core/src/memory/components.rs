@@ -1,16 +1,19 @@
 use crate::{
     apu::Apu,
+    boot_rom::BootRomOverlay,
     cartridge::Cartridge,
     interrupt::InterruptController,
     joypad::Joypad,
-    memory::{Dma, Hram, Wram},
+    memory::{Dma, Hdma, Hram, Wram},
     ppu::Ppu,
     serial::Serial,
+    speed::Speed,
     timer::{Divider, Timer},
 };
 
 #[derive(Debug)]
 pub struct Components<'a> {
+    pub boot_rom_overlay: &'a mut BootRomOverlay,
     pub cartridge: &'a mut Cartridge,
     pub wram: &'a mut Wram,
     pub ppu: &'a mut Ppu,
@@ -22,4 +25,16 @@ pub struct Components<'a> {
     pub timer: &'a mut Timer,
     pub serial: &'a mut Serial,
     pub dma: &'a mut Dma,
+    pub hdma: &'a mut Hdma,
+    pub speed: &'a mut Speed,
+}
+
+impl Components<'_> {
+    pub fn set_dmg_compatibility_mode(&mut self, is_compatible: bool) {
+        self.ppu.set_dmg_compatibility_mode(is_compatible);
+        self.serial.set_cgb_mode(!is_compatible);
+        self.wram.set_banked(!is_compatible);
+        self.hdma.set_enabled(!is_compatible);
+        self.speed.set_switchable(!is_compatible);
+    }
 }
@@ -1,3 +1,5 @@
+use crate::snapshot;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Process {
     source: u16,
@@ -56,3 +58,15 @@ impl Dma {
         }
     }
 }
+
+snapshot::fields!(Process {
+    source,
+    destination,
+    length,
+    transfered,
+});
+
+snapshot::fields!(Dma {
+    running_process,
+    queued_process,
+});
@@ -0,0 +1,128 @@
+use crate::{snapshot, util::bits::Bits};
+
+const BLOCK_SIZE: u16 = 0x10;
+
+const VRAM_START: u16 = 0x8000;
+
+const M_CYCLES_PER_BLOCK: u64 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Block {
+    pub source: u16,
+    pub destination: u16,
+    pub length: u16,
+}
+
+impl Block {
+    pub fn m_cycles(&self) -> u64 {
+        (self.length / BLOCK_SIZE) as u64 * M_CYCLES_PER_BLOCK
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hdma {
+    is_enabled: bool,
+    source: u16,
+    destination: u16,
+    rest_blocks: u16,
+    is_general: bool,
+    is_active: bool,
+    was_in_hblank: bool,
+}
+
+impl Hdma {
+    pub fn new(is_enabled: bool) -> Self {
+        Self {
+            is_enabled,
+            source: 0x0000,
+            destination: 0x0000,
+            rest_blocks: 0,
+            is_general: false,
+            is_active: false,
+            was_in_hblank: false,
+        }
+    }
+
+    pub fn set_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+        if !is_enabled {
+            self.is_active = false;
+        }
+    }
+
+    pub fn set_source_upper(&mut self, value: u8) {
+        self.source = (value as u16) << 8 | self.source & 0x00FF;
+    }
+
+    pub fn set_source_lower(&mut self, value: u8) {
+        self.source = self.source & 0xFF00 | (value & 0xF0) as u16;
+    }
+
+    pub fn set_destination_upper(&mut self, value: u8) {
+        self.destination = ((value & 0x1F) as u16) << 8 | self.destination & 0x00FF;
+    }
+
+    pub fn set_destination_lower(&mut self, value: u8) {
+        self.destination = self.destination & 0xFF00 | (value & 0xF0) as u16;
+    }
+
+    pub fn control_bits(&self) -> u8 {
+        if !self.is_enabled {
+            0xFF
+        } else {
+            (!self.is_active as u8) << 7 | (self.rest_blocks.wrapping_sub(1) & 0x7F) as u8
+        }
+    }
+
+    pub fn set_control_bits(&mut self, value: u8) {
+        if !self.is_enabled {
+            return;
+        }
+        if self.is_active && !self.is_general && !value.bit(7) {
+            self.is_active = false;
+            return;
+        }
+        self.rest_blocks = (value & 0x7F) as u16 + 1;
+        self.is_general = !value.bit(7);
+        self.is_active = true;
+    }
+
+    fn take_blocks(&mut self, count: u16) -> Block {
+        let length = count * BLOCK_SIZE;
+        let block = Block {
+            source: self.source,
+            destination: VRAM_START | self.destination,
+            length,
+        };
+        self.source = self.source.wrapping_add(length);
+        self.destination = (self.destination + length) & 0x1FF0;
+        self.rest_blocks -= count;
+        if self.rest_blocks == 0 {
+            self.is_active = false;
+        }
+        block
+    }
+
+    pub fn tick(&mut self, is_in_hblank: bool) -> Option<Block> {
+        let enters_hblank = is_in_hblank && !self.was_in_hblank;
+        self.was_in_hblank = is_in_hblank;
+        if !self.is_active {
+            None
+        } else if self.is_general {
+            Some(self.take_blocks(self.rest_blocks))
+        } else if enters_hblank {
+            Some(self.take_blocks(1))
+        } else {
+            None
+        }
+    }
+}
+
+snapshot::fields!(Hdma {
+    source,
+    destination,
+    rest_blocks,
+    is_general,
+    is_active,
+    was_in_hblank,
+});
@@ -1,17 +1,19 @@
+use crate::snapshot;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Hram {
     data: Vec<u8>,
 }
 
-impl Default for Hram {
-    fn default() -> Self {
+const SIZE: usize = 0x7F;
+
+impl Hram {
+    pub fn new(fill: impl Fn(usize) -> u8) -> Self {
         Self {
-            data: vec![0x00u8; 0x7F],
+            data: (0..SIZE).map(fill).collect(),
         }
     }
-}
 
-impl Hram {
     pub fn read(&self, address: u16) -> u8 {
         *self.data.get(address as usize).unwrap_or_else(|| {
             log::warn!("HRAM: Attempt to read from out of bounds: {:04X}", address);
@@ -26,3 +28,5 @@ impl Hram {
         }
     }
 }
+
+snapshot::fields!(Hram { data });
@@ -0,0 +1,27 @@
+use super::{Segment, CARTRIDGE};
+
+pub const BOOT_ROM: Segment = Segment::Leaf(
+    |components, address| {
+        components
+            .boot_rom_overlay
+            .read(address)
+            .unwrap_or_else(|| CARTRIDGE.read(components, address))
+    },
+    |components, address, value| CARTRIDGE.write(components, address, value),
+);
+
+pub const BOOT_ROM_CONTROL: Segment = Segment::Leaf(
+    |components, _| components.boot_rom_overlay.bits(),
+    |components, _, value| {
+        let was_mapped = components.boot_rom_overlay.is_mapped();
+        components.boot_rom_overlay.set_bits(value);
+        if was_mapped && components.boot_rom_overlay.is_dmg_compatible() {
+            components.set_dmg_compatibility_mode(true);
+        }
+    },
+);
+
+pub const COMPATIBILITY_CONTROL: Segment = Segment::Leaf(
+    |_, _| 0xFF,
+    |components, _, value| components.boot_rom_overlay.set_compatibility_bits(value),
+);
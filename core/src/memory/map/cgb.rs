@@ -1,6 +1,9 @@
 use super::Segment;
 
 pub const CGB_REGISTERS: Segment = Segment::Nested(|address| match address {
-    0xFF4D => &Segment::Leaf(|_, _| 0xFF, |_, _, _| {}),
+    0xFF4D => &Segment::Leaf(
+        |components, _| components.speed.bits(),
+        |components, _, value| components.speed.set_bits(value),
+    ),
     _ => &Segment::Leaf(|_, _| 0xFF, |_, _, _| {}),
 });
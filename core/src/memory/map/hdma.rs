@@ -0,0 +1,25 @@
+use super::Segment;
+
+pub const HDMA: Segment = Segment::Nested(|address| match address {
+    0xFF51 => &Segment::Leaf(
+        |_, _| 0xFF,
+        |components, _, value| components.hdma.set_source_upper(value),
+    ),
+    0xFF52 => &Segment::Leaf(
+        |_, _| 0xFF,
+        |components, _, value| components.hdma.set_source_lower(value),
+    ),
+    0xFF53 => &Segment::Leaf(
+        |_, _| 0xFF,
+        |components, _, value| components.hdma.set_destination_upper(value),
+    ),
+    0xFF54 => &Segment::Leaf(
+        |_, _| 0xFF,
+        |components, _, value| components.hdma.set_destination_lower(value),
+    ),
+    0xFF55 => &Segment::Leaf(
+        |components, _| components.hdma.control_bits(),
+        |components, _, value| components.hdma.set_control_bits(value),
+    ),
+    _ => unreachable!(),
+});
@@ -1,6 +1,8 @@
 mod apu;
+mod boot_rom;
 mod cartridge;
 mod cgb;
+mod hdma;
 mod hram;
 mod interrupt;
 mod ir;
@@ -14,8 +16,10 @@ mod unusable;
 mod wram;
 
 use apu::APU;
+use boot_rom::{BOOT_ROM, BOOT_ROM_CONTROL, COMPATIBILITY_CONTROL};
 use cartridge::CARTRIDGE;
 use cgb::CGB_REGISTERS;
+use hdma::HDMA;
 use hram::HRAM;
 use interrupt::{INTERRUPT_ENABLED, INTERRUPT_REQUESTED};
 use ir::IR;
@@ -30,7 +34,8 @@ use wram::WRAM;
 
 pub const ROOT: Segment = Segment::Nested(|address| {
     match address {
-        0x0000..=0x7FFF => &CARTRIDGE,
+        0x0000..=0x08FF => &BOOT_ROM,
+        0x0900..=0x7FFF => &CARTRIDGE,
         0x8000..=0x9FFF => &VRAM,
         0xA000..=0xBFFF => &CARTRIDGE,
         0xC000..=0xFDFF => &WRAM,
@@ -43,14 +48,17 @@ pub const ROOT: Segment = Segment::Nested(|address| {
         0xFF08..=0xFF0E => &UNKNOWN,
         0xFF0F => &INTERRUPT_REQUESTED,
         0xFF10..=0xFF3F => &APU,
-        0xFF40..=0xFF4C => &PPU,
+        0xFF40..=0xFF4B => &PPU,
+        0xFF4C => &COMPATIBILITY_CONTROL,
         0xFF4D => &CGB_REGISTERS,
-        0xFF4E..=0xFF55 => &PPU,
+        0xFF4E..=0xFF4F => &PPU,
+        0xFF50 => &BOOT_ROM_CONTROL,
+        0xFF51..=0xFF55 => &HDMA,
         0xFF56 => &IR,
         0xFF57..=0xFF67 => &UNKNOWN,
         0xFF68..=0xFF6C => &PPU,
         0xFF6D..=0xFF6F => &UNKNOWN,
-        0xFF70 => &WRAM, // CGB: WRAM Bank
+        0xFF70 => &WRAM,
         0xFF71 => &UNKNOWN,
         0xFF72..=0xFF75 => &UNKNOWN, // undocumented registers?
         0xFF76..=0xFF77 => &APU,
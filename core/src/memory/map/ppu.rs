@@ -1,7 +1,4 @@
-use super::{
-    Segment::{self, *},
-    UNKNOWN,
-};
+use super::Segment::{self, *};
 
 pub const VRAM: Segment = Offset(
     0x8000,
@@ -71,12 +68,66 @@ pub const PPU: Segment = Nested(|address| match address {
         |components, _| components.ppu.window_position().x,
         |components, _, value| components.ppu.window_position_mut().x = value,
     ),
-    0xFF4C => &Leaf(|_, _| 0, |_, _, _| {}),
     0xFF4E => &Leaf(|_, _| 0, |_, _, _| {}),
-    0xFF4F => &Leaf(|_, _| 0, |_, _, _| {}), // CGB: VRAM Bank
-    0xFF50 => &UNKNOWN,
-    0xFF51..=0xFF55 => &Leaf(|_, _| 0, |_, _, _| {}), // CGB: DMA
-    0xFF68..=0xFF6B => &Leaf(|_, _| 0, |_, _, _| {}), // CGB: Color Palettes
-    0xFF6C => &Leaf(|_, _| 0, |_, _, _| {}),          // CGB: Object Priority Mode
+    0xFF4F => &Leaf(
+        |components, _| components.ppu.vram().bank_bits(),
+        |components, _, value| components.ppu.vram_mut().set_bank_bits(value),
+    ),
+    0xFF68 => &Leaf(
+        |components, _| {
+            components
+                .ppu
+                .background_color_palettes()
+                .map_or(0xFF, |palettes| palettes.specification_bits())
+        },
+        |components, _, value| {
+            if let Some(palettes) = components.ppu.background_color_palettes_mut() {
+                palettes.set_specification_bits(value);
+            }
+        },
+    ),
+    0xFF69 => &Leaf(
+        |components, _| {
+            components
+                .ppu
+                .background_color_palettes()
+                .map_or(0xFF, |palettes| palettes.data())
+        },
+        |components, _, value| {
+            if let Some(palettes) = components.ppu.background_color_palettes_mut() {
+                palettes.set_data(value);
+            }
+        },
+    ),
+    0xFF6A => &Leaf(
+        |components, _| {
+            components
+                .ppu
+                .object_color_palettes()
+                .map_or(0xFF, |palettes| palettes.specification_bits())
+        },
+        |components, _, value| {
+            if let Some(palettes) = components.ppu.object_color_palettes_mut() {
+                palettes.set_specification_bits(value);
+            }
+        },
+    ),
+    0xFF6B => &Leaf(
+        |components, _| {
+            components
+                .ppu
+                .object_color_palettes()
+                .map_or(0xFF, |palettes| palettes.data())
+        },
+        |components, _, value| {
+            if let Some(palettes) = components.ppu.object_color_palettes_mut() {
+                palettes.set_data(value);
+            }
+        },
+    ),
+    0xFF6C => &Leaf(
+        |components, _| components.ppu.object_priority_mode_bits(),
+        |components, _, value| components.ppu.set_object_priority_mode_bits(value),
+    ),
     _ => unreachable!(),
 });
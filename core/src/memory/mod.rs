@@ -1,5 +1,7 @@
 pub mod dma;
+pub mod hdma;
 pub mod hram;
+pub mod watcher;
 pub mod wram;
 
 mod components;
@@ -7,13 +9,15 @@ mod map;
 
 pub use components::Components;
 pub use dma::Dma;
+pub use hdma::Hdma;
 pub use hram::Hram;
 pub use map::ROOT;
+pub use watcher::{Access, AccessKind, Watcher, Watchpoint};
 pub use wram::Wram;
 
-use std::fmt;
+use std::{cell::RefCell, fmt};
 
-pub struct Memory<'a>(Components<'a>);
+pub struct Memory<'a>(Components<'a>, Option<&'a RefCell<Watcher>>);
 
 impl<'a> fmt::Debug for Memory<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -23,7 +27,21 @@ impl<'a> fmt::Debug for Memory<'a> {
 
 impl<'a> Memory<'a> {
     pub fn new(components: Components<'a>) -> Self {
-        Self(components)
+        Self(components, None)
+    }
+
+    pub fn watched(components: Components<'a>, watcher: &'a RefCell<Watcher>) -> Self {
+        Self(components, Some(watcher))
+    }
+
+    fn notify(&self, kind: AccessKind, address: u16, value: u8) {
+        if let Some(watcher) = self.1 {
+            watcher.borrow_mut().notify(Access {
+                kind,
+                address,
+                value,
+            });
+        }
     }
 
     pub fn components(&self) -> &Components {
@@ -35,18 +53,35 @@ impl<'a> Memory<'a> {
     }
 
     pub fn read(&self, address: u16) -> u8 {
+        let value = self.peek(address);
+        self.notify(AccessKind::Read, address, value);
+        value
+    }
+
+    pub fn peek(&self, address: u16) -> u8 {
         ROOT.read(&self.0, address)
     }
 
     pub fn write(&mut self, address: u16, value: u8) {
-        ROOT.write(&mut self.0, address, value)
+        ROOT.write(&mut self.0, address, value);
+        self.notify(AccessKind::Write, address, value);
     }
 
-    pub fn tick(&mut self) {
+    pub fn tick(&mut self) -> u64 {
         if let Some(dma_process) = self.0.dma.running_process().clone() {
             let value = self.read(dma_process.next_source());
             self.write(dma_process.next_destination(), value);
         }
-        self.0.dma.tick()
+        self.0.dma.tick();
+        let is_in_hblank = self.0.ppu.is_in_hblank();
+        if let Some(block) = self.0.hdma.tick(is_in_hblank) {
+            for offset in 0..block.length {
+                let value = self.read(block.source.wrapping_add(offset));
+                self.write(block.destination.wrapping_add(offset), value);
+            }
+            block.m_cycles() * self.0.speed.m_cycles_per_tick()
+        } else {
+            0
+        }
     }
 }
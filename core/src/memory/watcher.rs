@@ -0,0 +1,174 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    Read,
+    Write,
+}
+
+impl fmt::Display for AccessKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessKind::Read => write!(f, "read"),
+            AccessKind::Write => write!(f, "write"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Access {
+    pub kind: AccessKind,
+    pub address: u16,
+    pub value: u8,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:04X} = {:02X}", self.kind, self.address, self.value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Watchpoint {
+    pub start: u16,
+    pub end: u16,
+    pub kind: Option<AccessKind>,
+    pub value: Option<u8>,
+}
+
+impl Watchpoint {
+    pub fn new(address: u16) -> Self {
+        Self {
+            start: address,
+            end: address,
+            kind: None,
+            value: None,
+        }
+    }
+
+    pub fn matches(&self, access: &Access) -> bool {
+        (self.start..=self.end).contains(&access.address)
+            && self.kind.is_none_or(|kind| kind == access.kind)
+            && self.value.is_none_or(|value| value == access.value)
+    }
+}
+
+impl fmt::Display for Watchpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04X}", self.start)?;
+        if self.end != self.start {
+            write!(f, "-{:04X}", self.end)?;
+        }
+        match self.kind {
+            Some(AccessKind::Read) => write!(f, " r")?,
+            Some(AccessKind::Write) => write!(f, " w")?,
+            None => write!(f, " rw")?,
+        }
+        if let Some(value) = self.value {
+            write!(f, " ={:02X}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Watchpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid watchpoint: {}", s);
+        let parse_address = |value: &str| {
+            u16::from_str_radix(value.trim_start_matches('$'), 16).map_err(|_| error())
+        };
+        let mut words = s.split_whitespace();
+        let range = words.next().ok_or_else(error)?;
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (parse_address(start)?, parse_address(end)?),
+            None => (parse_address(range)?, parse_address(range)?),
+        };
+        if end < start {
+            return Err(error());
+        }
+        let mut watchpoint = Self {
+            start,
+            end,
+            ..Self::new(start)
+        };
+        for word in words {
+            match word {
+                "r" => watchpoint.kind = Some(AccessKind::Read),
+                "w" => watchpoint.kind = Some(AccessKind::Write),
+                "rw" => watchpoint.kind = None,
+                _ => {
+                    let value = word.strip_prefix('=').ok_or_else(error)?;
+                    watchpoint.value = Some(
+                        u8::from_str_radix(value.trim_start_matches('$'), 16)
+                            .map_err(|_| error())?,
+                    );
+                }
+            }
+        }
+        Ok(watchpoint)
+    }
+}
+
+type Hook = Box<dyn FnMut(&Access)>;
+
+#[derive(Default)]
+pub struct Watcher {
+    watchpoints: Vec<Watchpoint>,
+    hit: Option<Access>,
+    hook: Option<Hook>,
+}
+
+impl fmt::Debug for Watcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watcher")
+            .field("watchpoints", &self.watchpoints)
+            .field("hit", &self.hit)
+            .finish()
+    }
+}
+
+impl Watcher {
+    pub fn watchpoints(&self) -> impl Iterator<Item = &Watchpoint> {
+        self.watchpoints.iter()
+    }
+
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+    }
+
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        let length = self.watchpoints.len();
+        self.watchpoints.retain(|current| current != watchpoint);
+        self.watchpoints.len() != length
+    }
+
+    pub fn set_hook(&mut self, hook: impl FnMut(&Access) + 'static) {
+        self.hook = Some(Box::new(hook));
+    }
+
+    pub fn clear_hook(&mut self) {
+        self.hook = None;
+    }
+
+    pub fn take_hit(&mut self) -> Option<Access> {
+        self.hit.take()
+    }
+
+    pub(crate) fn notify(&mut self, access: Access) {
+        if !self
+            .watchpoints
+            .iter()
+            .any(|watchpoint| watchpoint.matches(&access))
+        {
+            return;
+        }
+        self.hit.get_or_insert(access);
+        if let Some(hook) = &mut self.hook {
+            hook(&access);
+        }
+    }
+}
@@ -1,21 +1,34 @@
+use crate::snapshot;
 use log;
 
+const BANK_SIZE: usize = 0x1000;
+
+const SWITCHABLE_BANK_COUNT: usize = 7;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Wram {
     primary: Vec<u8>,
-    bank: Vec<u8>,
+    banks: Vec<u8>,
+    is_banked: bool,
+    bank_number: u8,
 }
 
-impl Default for Wram {
-    fn default() -> Self {
+impl Wram {
+    pub fn new(is_banked: bool, fill: impl Fn(usize) -> u8) -> Self {
         Self {
-            primary: vec![0x00u8; 0x1000],
-            bank: vec![0x00u8; 0x1000],
+            primary: (0..BANK_SIZE).map(&fill).collect(),
+            banks: (BANK_SIZE..BANK_SIZE * (SWITCHABLE_BANK_COUNT + 1))
+                .map(&fill)
+                .collect(),
+            is_banked,
+            bank_number: 1,
         }
     }
-}
 
-impl Wram {
+    fn bank_address(&self, address: u16) -> usize {
+        (self.bank_number as usize).saturating_sub(1) * BANK_SIZE + address as usize
+    }
+
     pub fn read(&self, address: u16) -> u8 {
         *self.primary.get(address as usize).unwrap_or_else(|| {
             log::warn!("WRAM: Attempt to read from out of bounds: {:04X}", address);
@@ -31,17 +44,21 @@ impl Wram {
     }
 
     pub fn read_bank(&self, address: u16) -> u8 {
-        *self.bank.get(address as usize).unwrap_or_else(|| {
-            log::warn!(
-                "WRAM: (Bank) Attempt to read from out of bounds: {:04X}",
-                address
-            );
-            &0xFF
-        })
+        *self
+            .banks
+            .get(self.bank_address(address))
+            .unwrap_or_else(|| {
+                log::warn!(
+                    "WRAM: (Bank) Attempt to read from out of bounds: {:04X}",
+                    address
+                );
+                &0xFF
+            })
     }
 
     pub fn write_bank(&mut self, address: u16, value: u8) {
-        match self.bank.get_mut(address as usize) {
+        let bank_address = self.bank_address(address);
+        match self.banks.get_mut(bank_address) {
             Some(e) => *e = value,
             None => log::warn!(
                 "WRAM: (Bank) Attempt to write to out of bounds: {:04X}",
@@ -50,9 +67,43 @@ impl Wram {
         }
     }
 
+    pub fn write_to_bank(&mut self, bank_number: u8, address: u16, value: u8) {
+        let bank_number = std::cmp::max(1, bank_number & 0b111);
+        let bank_address = (bank_number as usize - 1) * BANK_SIZE + address as usize;
+        match self.banks.get_mut(bank_address) {
+            Some(e) => *e = value,
+            None => log::warn!(
+                "WRAM: (Bank {}) Attempt to write to out of bounds: {:04X}",
+                bank_number,
+                address
+            ),
+        }
+    }
+
+    pub fn set_banked(&mut self, is_banked: bool) {
+        self.is_banked = is_banked;
+        if !is_banked {
+            self.bank_number = 1;
+        }
+    }
+
     pub fn bank_switch(&self) -> u8 {
-        0x00
+        if self.is_banked {
+            0b1111_1000 | self.bank_number
+        } else {
+            0xFF
+        }
     }
 
-    pub fn set_bank_switch(&self, _value: u8) {}
+    pub fn set_bank_switch(&mut self, value: u8) {
+        if self.is_banked {
+            self.bank_number = std::cmp::max(1, value & 0b111);
+        }
+    }
 }
+
+snapshot::fields!(Wram {
+    primary,
+    banks,
+    bank_number,
+});
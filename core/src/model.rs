@@ -0,0 +1,168 @@
+use crate::{
+    cartridge::Header,
+    cpu::Registers,
+    snapshot::{Error, Reader, Snapshot, Writer},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Model {
+    Dmg0,
+    Dmg,
+    Mgb,
+    Sgb,
+    Sgb2,
+    Cgb,
+    Agb,
+}
+
+fn noise(seed: u32, index: usize) -> u8 {
+    let mut x = (index as u32).wrapping_mul(0x9E37_79B9) ^ seed;
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    (x >> 8) as u8
+}
+
+impl Model {
+    pub fn is_cgb(&self) -> bool {
+        use Model::*;
+        matches!(self, Cgb | Agb)
+    }
+
+    pub fn is_sgb(&self) -> bool {
+        use Model::*;
+        matches!(self, Sgb | Sgb2)
+    }
+
+    pub(crate) fn registers(&self, header: &Header, is_cgb_mode: bool) -> Registers {
+        use Model::*;
+        let a = match self {
+            Dmg0 | Dmg | Sgb => 0x01,
+            Mgb | Sgb2 => 0xFF,
+            Cgb | Agb => 0x11,
+        };
+        let (b, c, d, e, h, l) = match self {
+            Dmg0 => (0xFF, 0x13, 0x00, 0xC1, 0x84, 0x03),
+            Dmg | Mgb => (0x00, 0x13, 0x00, 0xD8, 0x01, 0x4D),
+            Sgb | Sgb2 => (0x00, 0x14, 0x00, 0x00, 0xC0, 0x60),
+            Cgb | Agb if is_cgb_mode => (0x00, 0x00, 0xFF, 0x56, 0x00, 0x0D),
+            Cgb | Agb => {
+                let b = if header.licensee.is_nintendo() {
+                    header
+                        .title
+                        .bytes()
+                        .iter()
+                        .fold(0x00u8, |acc, &byte| acc.wrapping_add(byte))
+                } else {
+                    0x00
+                };
+                let (h, l) = if b == 0x43 || b == 0x58 {
+                    (0x99, 0x1A)
+                } else {
+                    (0x00, 0x7C)
+                };
+                (b, 0x00, 0x00, 0x08, h, l)
+            }
+        };
+        let mut registers = Registers {
+            a,
+            b,
+            c,
+            d,
+            e,
+            h,
+            l,
+            pc: 0x0100,
+            sp: 0xFFFE,
+            ..Default::default()
+        };
+        match self {
+            Dmg | Mgb => {
+                let has_checksum = header.header_checksum.value() != 0x00;
+                registers.f.z = true;
+                registers.f.h = has_checksum;
+                registers.f.c = has_checksum;
+            }
+            Cgb => registers.f.z = true,
+            Agb => {
+                registers.b = registers.b.wrapping_add(1);
+                registers.f.z = registers.b == 0x00;
+                registers.f.h = registers.b & 0x0F == 0x00;
+            }
+            _ => {}
+        }
+        registers
+    }
+
+    pub(crate) fn divider_counter(&self, is_cgb_mode: bool) -> u16 {
+        use Model::*;
+        match self {
+            Dmg0 => 0x1830,
+            Dmg | Mgb => 0xABCC,
+            Sgb | Sgb2 => 0x0000,
+            Cgb | Agb if is_cgb_mode => 0x1EA0,
+            Cgb | Agb => 0x267C,
+        }
+    }
+
+    pub(crate) fn current_line(&self) -> u8 {
+        if self.is_cgb() {
+            0x90
+        } else {
+            0x00
+        }
+    }
+
+    pub(crate) fn plays_boot_sound(&self) -> bool {
+        !self.is_sgb()
+    }
+
+    pub(crate) fn wram_fill(&self, index: usize) -> u8 {
+        if self.is_cgb() {
+            if (index / 8).is_multiple_of(2) {
+                0x00
+            } else {
+                0xFF
+            }
+        } else {
+            noise(*self as u32, index)
+        }
+    }
+
+    pub(crate) fn hram_fill(&self, index: usize) -> u8 {
+        noise(!(*self as u32), index)
+    }
+}
+
+impl Snapshot for Model {
+    fn save(&self, writer: &mut Writer) {
+        use Model::*;
+        (match self {
+            Dmg0 => 0u8,
+            Dmg => 1,
+            Mgb => 2,
+            Sgb => 3,
+            Sgb2 => 4,
+            Cgb => 5,
+            Agb => 6,
+        })
+        .save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        use Model::*;
+        let mut value = 0u8;
+        value.load(reader)?;
+        *self = match value {
+            0 => Dmg0,
+            1 => Dmg,
+            2 => Mgb,
+            3 => Sgb,
+            4 => Sgb2,
+            5 => Cgb,
+            6 => Agb,
+            _ => return Err(Error::InvalidValue),
+        };
+        Ok(())
+    }
+}
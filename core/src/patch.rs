@@ -0,0 +1,251 @@
+use crate::util::crc32::crc32;
+use std::path::{Path, PathBuf};
+
+pub const EXTENSIONS: [&str; 3] = ["ips", "ups", "bps"];
+
+const IPS_MAGIC: &[u8] = b"PATCH";
+
+const IPS_END: &[u8] = b"EOF";
+
+const UPS_MAGIC: &[u8] = b"UPS1";
+
+const BPS_MAGIC: &[u8] = b"BPS1";
+
+const FOOTER_LENGTH: usize = 12;
+
+const MAX_TARGET_SIZE: usize = 0x0100_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    UnknownFormat,
+    UnexpectedEnd,
+    InvalidOffset,
+    TooLarge,
+    SourceChecksumMismatch,
+    TargetChecksumMismatch,
+    PatchChecksumMismatch,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn read(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + length)
+            .ok_or(Error::UnexpectedEnd)?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_be(&mut self, length: usize) -> Result<usize, Error> {
+        Ok(self
+            .read(length)?
+            .iter()
+            .fold(0, |value, byte| value << 8 | *byte as usize))
+    }
+
+    fn read_u32_le(&mut self) -> Result<u32, Error> {
+        let bytes = self.read(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_number(&mut self) -> Result<usize, Error> {
+        let mut value = 0usize;
+        let mut shift = 1usize;
+        loop {
+            let byte = self.read_u8()?;
+            value = (byte as usize & 0x7F)
+                .checked_mul(shift)
+                .and_then(|data| value.checked_add(data))
+                .ok_or(Error::InvalidOffset)?;
+            if byte & 0x80 != 0 {
+                return Ok(value);
+            }
+            shift = shift.checked_shl(7).ok_or(Error::InvalidOffset)?;
+            value = value.checked_add(shift).ok_or(Error::InvalidOffset)?;
+        }
+    }
+
+    fn read_offset(&mut self) -> Result<isize, Error> {
+        let data = self.read_number()?;
+        let offset = (data >> 1) as isize;
+        Ok(if data & 1 != 0 { -offset } else { offset })
+    }
+}
+
+struct Footer {
+    source_checksum: u32,
+    target_checksum: u32,
+}
+
+fn read_footer(patch: &[u8]) -> Result<Footer, Error> {
+    let body_length = patch
+        .len()
+        .checked_sub(FOOTER_LENGTH)
+        .ok_or(Error::UnexpectedEnd)?;
+    let mut reader = Reader::new(patch, body_length);
+    let footer = Footer {
+        source_checksum: reader.read_u32_le()?,
+        target_checksum: reader.read_u32_le()?,
+    };
+    if reader.read_u32_le()? != crc32(&patch[..patch.len() - 4]) {
+        return Err(Error::PatchChecksumMismatch);
+    }
+    Ok(footer)
+}
+
+fn apply_ips(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut output = rom.to_vec();
+    let mut reader = Reader::new(patch, IPS_MAGIC.len());
+    loop {
+        let offset_bytes = reader.read(3)?;
+        if offset_bytes == IPS_END {
+            break;
+        }
+        let offset = offset_bytes
+            .iter()
+            .fold(0, |value, byte| value << 8 | *byte as usize);
+        let (length, value) = match reader.read_be(2)? {
+            0 => (reader.read_be(2)?, Some(reader.read_u8()?)),
+            length => (length, None),
+        };
+        if offset + length > MAX_TARGET_SIZE {
+            return Err(Error::TooLarge);
+        }
+        if output.len() < offset + length {
+            output.resize(offset + length, 0x00);
+        }
+        match value {
+            Some(value) => output[offset..offset + length].fill(value),
+            None => output[offset..offset + length].copy_from_slice(reader.read(length)?),
+        }
+    }
+    if let Ok(length) = reader.read_be(3) {
+        output.truncate(length);
+    }
+    Ok(output)
+}
+
+fn apply_ups(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, Error> {
+    let footer = read_footer(patch)?;
+    let body_length = patch.len() - FOOTER_LENGTH;
+    let mut reader = Reader::new(&patch[..body_length], UPS_MAGIC.len());
+    let source_size = reader.read_number()?;
+    let target_size = reader.read_number()?;
+    if target_size > MAX_TARGET_SIZE {
+        return Err(Error::TooLarge);
+    }
+    if rom.len() != source_size || crc32(rom) != footer.source_checksum {
+        return Err(Error::SourceChecksumMismatch);
+    }
+    let source = |position: usize| rom.get(position).copied().unwrap_or_default();
+    let mut output: Vec<u8> = (0..target_size).map(source).collect();
+    let mut position = 0usize;
+    while reader.position < body_length {
+        position = position
+            .checked_add(reader.read_number()?)
+            .ok_or(Error::InvalidOffset)?;
+        loop {
+            let value = reader.read_u8()?;
+            if let Some(output) = output.get_mut(position) {
+                *output = source(position) ^ value;
+            }
+            position += 1;
+            if value == 0 {
+                break;
+            }
+        }
+    }
+    if crc32(&output) != footer.target_checksum {
+        return Err(Error::TargetChecksumMismatch);
+    }
+    Ok(output)
+}
+
+fn advance_offset(offset: &mut isize, delta: isize, length: usize) -> Result<usize, Error> {
+    *offset += delta;
+    let start = usize::try_from(*offset).map_err(|_| Error::InvalidOffset)?;
+    *offset += length as isize;
+    Ok(start)
+}
+
+fn apply_bps(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, Error> {
+    let footer = read_footer(patch)?;
+    let body_length = patch.len() - FOOTER_LENGTH;
+    let mut reader = Reader::new(&patch[..body_length], BPS_MAGIC.len());
+    let source_size = reader.read_number()?;
+    let target_size = reader.read_number()?;
+    let metadata_size = reader.read_number()?;
+    reader.read(metadata_size)?;
+    if target_size > MAX_TARGET_SIZE {
+        return Err(Error::TooLarge);
+    }
+    if rom.len() != source_size || crc32(rom) != footer.source_checksum {
+        return Err(Error::SourceChecksumMismatch);
+    }
+    let mut output = Vec::with_capacity(target_size);
+    let mut source_offset = 0isize;
+    let mut target_offset = 0isize;
+    while reader.position < body_length {
+        let data = reader.read_number()?;
+        let length = (data >> 2) + 1;
+        match data & 0b11 {
+            0 => {
+                let start = output.len();
+                let bytes = rom.get(start..start + length).ok_or(Error::InvalidOffset)?;
+                output.extend_from_slice(bytes);
+            }
+            1 => output.extend_from_slice(reader.read(length)?),
+            2 => {
+                let start = advance_offset(&mut source_offset, reader.read_offset()?, length)?;
+                let bytes = rom.get(start..start + length).ok_or(Error::InvalidOffset)?;
+                output.extend_from_slice(bytes);
+            }
+            _ => {
+                let start = advance_offset(&mut target_offset, reader.read_offset()?, length)?;
+                for index in start..start + length {
+                    let value = *output.get(index).ok_or(Error::InvalidOffset)?;
+                    output.push(value);
+                }
+            }
+        }
+        if output.len() > target_size {
+            return Err(Error::InvalidOffset);
+        }
+    }
+    if output.len() != target_size || crc32(&output) != footer.target_checksum {
+        return Err(Error::TargetChecksumMismatch);
+    }
+    Ok(output)
+}
+
+pub fn apply(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, Error> {
+    if patch.starts_with(IPS_MAGIC) {
+        apply_ips(rom, patch)
+    } else if patch.starts_with(UPS_MAGIC) {
+        apply_ups(rom, patch)
+    } else if patch.starts_with(BPS_MAGIC) {
+        apply_bps(rom, patch)
+    } else {
+        Err(Error::UnknownFormat)
+    }
+}
+
+pub fn find(rom_path: &Path) -> Option<PathBuf> {
+    EXTENSIONS
+        .iter()
+        .map(|extension| rom_path.with_extension(extension))
+        .find(|path| path.is_file())
+}
@@ -1,5 +1,5 @@
 use super::vram::{TileDataArea, TileMapArea};
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Control {
@@ -65,3 +65,5 @@ impl Control {
         self.bits.bit(0)
     }
 }
+
+snapshot::fields!(Control { bits });
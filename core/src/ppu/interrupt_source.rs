@@ -1,4 +1,4 @@
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct InterruptSource {
@@ -36,3 +36,10 @@ impl InterruptSource {
         self.hblank
     }
 }
+
+snapshot::fields!(InterruptSource {
+    ly,
+    oam,
+    vblank,
+    hblank,
+});
@@ -7,16 +7,20 @@ mod vec2;
 pub mod oam;
 pub mod vram;
 
-pub use palette::{Color, Palette};
+pub use palette::{Color, ColorPalettes, Palette, Rgb};
 pub use renderer::{NoRenderer, Renderer};
 pub use vec2::Vec2;
 
 use control::Control;
 use interrupt_source::InterruptSource;
 use oam::{Oam, Object};
-use vram::{TileDataArea, TileMapArea, Vram};
+use vram::{TileAttributes, TileDataArea, TileMapArea, Vram};
 
-use crate::interrupt::{Interrupt, InterruptController};
+use crate::{
+    interrupt::{Interrupt, InterruptController},
+    snapshot,
+    util::bits::Bits,
+};
 
 const LINES_PER_FRAME: u8 = 154;
 
@@ -55,29 +59,44 @@ pub struct Ppu {
     scroll_position: Vec2,
     window_position: Vec2,
     cycles_in_line: u64,
+    is_cgb_mode: bool,
+    is_dmg_compatible: bool,
+    background_color_palettes: ColorPalettes,
+    object_color_palettes: ColorPalettes,
+    prioritizes_object_by_coordinate: bool,
 }
 
-impl Default for Ppu {
-    fn default() -> Self {
+impl Ppu {
+    pub fn new(is_cgb_mode: bool, current_line: u8) -> Self {
         Self {
-            vram: Default::default(),
+            vram: Vram::new(is_cgb_mode),
             oam: Oam::default(),
             control: Default::default(),
             interrupt_source: Default::default(),
             interrupt_requested: false,
-            current_position: Vec2::new(0, 0x91),
+            current_position: Vec2::new(0, current_line),
             y_compare: 0,
             background_palette: 0xFC.into(),
             object_palette0: Default::default(),
             object_palette1: Default::default(),
             scroll_position: Default::default(),
             window_position: Default::default(),
-            cycles_in_line: 0x91 * CYCLES_PER_LINE,
+            cycles_in_line: 0,
+            is_cgb_mode,
+            is_dmg_compatible: false,
+            background_color_palettes: ColorPalettes::new(0xFF),
+            object_color_palettes: ColorPalettes::new(0x00),
+            prioritizes_object_by_coordinate: !is_cgb_mode,
         }
     }
-}
 
-impl Ppu {
+    pub fn power_on(is_cgb_mode: bool) -> Self {
+        let mut result = Self::new(is_cgb_mode, 0);
+        result.control.set_bits(0x00);
+        result.background_palette = 0x00.into();
+        result
+    }
+
     fn mode(&self) -> Mode {
         use Mode::*;
         if !self.control.is_enabled() {
@@ -105,13 +124,19 @@ impl Ppu {
         } else {
             object.tile_id
         };
+        let bank_number = if self.is_cgb_mode {
+            object.tile_bank_number
+        } else {
+            0
+        };
         self.vram
-            .tile_data(TileDataArea::Origin, tile_id)
+            .tile_data(bank_number, TileDataArea::Origin, tile_id)
             .color_id(position_in_object)
     }
 
     fn drawn_object(&self) -> Option<(Object, u8)> {
-        self.oam
+        let mut objects = self
+            .oam
             .objects_at_position(self.current_position, self.control.uses_large_object())
             .filter_map(|object| {
                 let pixel = self.object_pixel(&object);
@@ -120,17 +145,21 @@ impl Ppu {
                 } else {
                     Some((object, pixel))
                 }
-            })
-            .min_by_key(|(object, _)| object.position.x)
+            });
+        if self.prioritizes_object_by_coordinate {
+            objects.min_by_key(|(object, _)| object.position.x)
+        } else {
+            objects.next()
+        }
     }
 
-    fn pixel_from_tile_map(&self, tile_map: TileMapArea, position: Vec2) -> u8 {
+    fn pixel_from_tile_map(&self, tile_map: TileMapArea, position: Vec2) -> (u8, TileAttributes) {
         self.vram
             .tile_map(tile_map, self.control.background_tile_data_area())
             .color_id(position)
     }
 
-    fn background_pixel(&self) -> u8 {
+    fn background_pixel(&self) -> (u8, TileAttributes) {
         self.pixel_from_tile_map(
             self.control.background_tile_map_area(),
             self.current_position.wrapping_add(self.scroll_position),
@@ -143,7 +172,7 @@ impl Ppu {
             && self.current_position.x.wrapping_add(WINDOW_OFFSET) >= self.window_position.x
     }
 
-    fn window_pixel(&self) -> u8 {
+    fn window_pixel(&self) -> (u8, TileAttributes) {
         self.pixel_from_tile_map(
             self.control.window_tile_map_area(),
             self.current_position
@@ -152,35 +181,61 @@ impl Ppu {
         )
     }
 
+    fn background_color(&self, pixel: u8, attributes: TileAttributes) -> Color {
+        if self.is_cgb_mode {
+            self.background_color_palettes
+                .apply(attributes.palette_number, pixel)
+        } else if self.is_dmg_compatible {
+            self.background_color_palettes
+                .apply(0, self.background_palette.shade(pixel))
+        } else {
+            self.background_palette.apply(pixel)
+        }
+    }
+
+    fn object_color(&self, object: &Object, pixel: u8) -> Color {
+        if self.is_cgb_mode {
+            self.object_color_palettes
+                .apply(object.color_palette_number, pixel)
+        } else if self.is_dmg_compatible {
+            let palette = match object.palette_number {
+                0 => &self.object_palette0,
+                _ => &self.object_palette1,
+            };
+            self.object_color_palettes
+                .apply(object.palette_number, palette.shade(pixel))
+        } else if object.palette_number == 0 {
+            self.object_palette0.apply(pixel)
+        } else {
+            self.object_palette1.apply(pixel)
+        }
+    }
+
     fn render_pixel(&self, renderer: &mut impl Renderer) {
-        let background_pixel = if self.control.background_and_window_enabled() {
-            if self.is_in_window() {
-                self.window_pixel()
+        let (background_pixel, background_attributes) =
+            if self.is_cgb_mode || self.control.background_and_window_enabled() {
+                if self.is_in_window() {
+                    self.window_pixel()
+                } else {
+                    self.background_pixel()
+                }
             } else {
-                self.background_pixel()
-            }
-        } else {
-            0x00
+                (0x00, Default::default())
+            };
+        let background_has_priority = |object: &Object| {
+            background_pixel != 0x00
+                && self.control.background_and_window_enabled()
+                && (background_attributes.has_priority || object.is_under_background)
         };
         let color = if self.control.object_enabled() {
             self.drawn_object()
         } else {
             None
         }
+        .filter(|(object, _)| !background_has_priority(object))
         .map_or_else(
-            || self.background_palette.apply(background_pixel),
-            |(object, object_pixel)| {
-                if object.is_under_background && background_pixel != 0x00 {
-                    self.background_palette.apply(background_pixel)
-                } else {
-                    (if object.palette_number == 0 {
-                        &self.object_palette0
-                    } else {
-                        &self.object_palette1
-                    })
-                    .apply(object_pixel)
-                }
-            },
+            || self.background_color(background_pixel, background_attributes),
+            |(object, object_pixel)| self.object_color(&object, object_pixel),
         );
         renderer.render(self.current_position, color)
     }
@@ -232,6 +287,14 @@ impl Ppu {
         self.request_lcd_stat_interrupt(interrupt_controller);
     }
 
+    pub fn is_in_hblank(&self) -> bool {
+        self.control.is_enabled() && self.mode() == Mode::HBlank
+    }
+
+    pub fn is_in_vblank(&self) -> bool {
+        self.control.is_enabled() && self.mode() == Mode::VBlank
+    }
+
     pub fn vram(&self) -> &Vram {
         &self.vram
     }
@@ -321,4 +384,59 @@ impl Ppu {
     pub fn window_position_mut(&mut self) -> &mut Vec2 {
         &mut self.window_position
     }
+
+    pub fn background_color_palettes(&self) -> Option<&ColorPalettes> {
+        Some(&self.background_color_palettes).filter(|_| self.is_cgb_mode)
+    }
+
+    pub fn background_color_palettes_mut(&mut self) -> Option<&mut ColorPalettes> {
+        Some(&mut self.background_color_palettes).filter(|_| self.is_cgb_mode)
+    }
+
+    pub fn object_color_palettes(&self) -> Option<&ColorPalettes> {
+        Some(&self.object_color_palettes).filter(|_| self.is_cgb_mode)
+    }
+
+    pub fn object_color_palettes_mut(&mut self) -> Option<&mut ColorPalettes> {
+        Some(&mut self.object_color_palettes).filter(|_| self.is_cgb_mode)
+    }
+
+    pub fn object_priority_mode_bits(&self) -> u8 {
+        if self.is_cgb_mode {
+            0b1111_1110 | self.prioritizes_object_by_coordinate as u8
+        } else {
+            0xFF
+        }
+    }
+
+    pub fn set_object_priority_mode_bits(&mut self, value: u8) {
+        if self.is_cgb_mode {
+            self.prioritizes_object_by_coordinate = value.bit(0);
+        }
+    }
+
+    pub fn set_dmg_compatibility_mode(&mut self, is_compatible: bool) {
+        self.is_cgb_mode = !is_compatible;
+        self.is_dmg_compatible = is_compatible;
+        self.vram.set_banked(!is_compatible);
+    }
 }
+
+snapshot::fields!(Ppu {
+    vram,
+    oam,
+    control,
+    interrupt_source,
+    interrupt_requested,
+    current_position,
+    y_compare,
+    background_palette,
+    object_palette0,
+    object_palette1,
+    scroll_position,
+    window_position,
+    cycles_in_line,
+    background_color_palettes,
+    object_color_palettes,
+    prioritizes_object_by_coordinate,
+});
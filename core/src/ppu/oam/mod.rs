@@ -3,7 +3,7 @@ pub mod object;
 pub use object::Object;
 
 use super::Vec2;
-use crate::memory::Dma;
+use crate::{memory::Dma, snapshot};
 
 const OBJECT_COUNT: usize = 40;
 
@@ -82,3 +82,8 @@ impl Oam {
             .filter(move |object| object.contains_x(position.x))
     }
 }
+
+snapshot::fields!(Oam {
+    dma_source_address_upper,
+    data,
+});
@@ -1,9 +1,33 @@
+use crate::{snapshot, util::bits::Bits};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl From<u16> for Rgb {
+    fn from(bits: u16) -> Self {
+        Self {
+            red: (bits & 0x1F) as u8,
+            green: (bits >> 5 & 0x1F) as u8,
+            blue: (bits >> 10 & 0x1F) as u8,
+        }
+    }
+}
+
+impl Rgb {
+    pub const MAX: u8 = 0x1F;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Color {
     White,
     LightGray,
     DarkGray,
     Black,
+    Rgb(Rgb),
 }
 
 impl From<u8> for Color {
@@ -37,7 +61,65 @@ impl Palette {
         self.bits = value;
     }
 
+    pub fn shade(&self, color_id: u8) -> u8 {
+        self.bits >> (color_id * 2) & 0b11
+    }
+
     pub fn apply(&self, color_id: u8) -> Color {
-        (self.bits >> (color_id * 2) & 0b11).into()
+        self.shade(color_id).into()
+    }
+}
+
+const COLOR_PALETTE_DATA_SIZE: usize = 64;
+
+const COLOR_PALETTE_SIZE: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColorPalettes {
+    data: [u8; COLOR_PALETTE_DATA_SIZE],
+    index: u8,
+    auto_increments: bool,
+}
+
+impl ColorPalettes {
+    pub fn new(initial_value: u8) -> Self {
+        Self {
+            data: [initial_value; COLOR_PALETTE_DATA_SIZE],
+            index: 0,
+            auto_increments: false,
+        }
+    }
+
+    pub fn specification_bits(&self) -> u8 {
+        (self.auto_increments as u8) << 7 | 0b0100_0000 | self.index
+    }
+
+    pub fn set_specification_bits(&mut self, value: u8) {
+        self.auto_increments = value.bit(7);
+        self.index = value & 0b0011_1111;
+    }
+
+    pub fn data(&self) -> u8 {
+        self.data[self.index as usize]
+    }
+
+    pub fn set_data(&mut self, value: u8) {
+        self.data[self.index as usize] = value;
+        if self.auto_increments {
+            self.index = (self.index + 1) % COLOR_PALETTE_DATA_SIZE as u8;
+        }
+    }
+
+    pub fn apply(&self, palette_number: u8, color_id: u8) -> Color {
+        let index = palette_number as usize * COLOR_PALETTE_SIZE + color_id as usize * 2;
+        Color::Rgb(u16::from_le_bytes([self.data[index], self.data[index + 1]]).into())
     }
 }
+
+snapshot::fields!(Palette { bits });
+
+snapshot::fields!(ColorPalettes {
+    data,
+    index,
+    auto_increments,
+});
@@ -1,3 +1,5 @@
+use crate::snapshot;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Vec2 {
     pub x: u8,
@@ -17,3 +19,5 @@ impl Vec2 {
         Self::new(self.x.wrapping_sub(other.x), self.y.wrapping_sub(other.y))
     }
 }
+
+snapshot::fields!(Vec2 { x, y });
@@ -1,4 +1,5 @@
 use super::Vec2;
+use crate::{snapshot, util::bits::Bits};
 use std::ops::RangeInclusive;
 
 const TILE_DATA_SIZE: usize = 16;
@@ -57,6 +58,31 @@ impl TileMapArea {
     }
 }
 
+const BANK_SIZE: usize = 0x2000;
+
+const BANK_COUNT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TileAttributes {
+    pub has_priority: bool,
+    pub is_flipped_y: bool,
+    pub is_flipped_x: bool,
+    pub bank_number: u8,
+    pub palette_number: u8,
+}
+
+impl From<u8> for TileAttributes {
+    fn from(bits: u8) -> Self {
+        Self {
+            has_priority: bits.bit(7),
+            is_flipped_y: bits.bit(6),
+            is_flipped_x: bits.bit(5),
+            bank_number: bits.bit(3) as u8,
+            palette_number: bits & 0b111,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct VramData {
     data: Vec<u8>,
@@ -65,19 +91,28 @@ struct VramData {
 impl Default for VramData {
     fn default() -> Self {
         Self {
-            data: vec![0x00u8; 0x2000],
+            data: vec![0x00u8; BANK_SIZE * BANK_COUNT],
         }
     }
 }
 
 impl VramData {
+    fn bank(&self, bank_number: u8) -> &[u8] {
+        let start = bank_number as usize * BANK_SIZE;
+        &self.data[start..start + BANK_SIZE]
+    }
+
     fn tile_map_data(&self, area: TileMapArea) -> &[u8] {
-        &self.data[area.range()]
+        &self.bank(0)[area.range()]
+    }
+
+    fn tile_attributes_data(&self, area: TileMapArea) -> &[u8] {
+        &self.bank(1)[area.range()]
     }
 
-    fn tile_data(&self, area: TileDataArea, id: u8) -> TileData {
+    fn tile_data(&self, bank_number: u8, area: TileDataArea, id: u8) -> TileData {
         let address = area.address(id);
-        TileData::new(&self.data[address..(address + TILE_DATA_SIZE)])
+        TileData::new(&self.bank(bank_number)[address..(address + TILE_DATA_SIZE)])
     }
 }
 
@@ -89,24 +124,56 @@ pub struct TileMap<'vram> {
 }
 
 impl<'vram> TileMap<'vram> {
+    fn index(position: Vec2) -> usize {
+        position.y as usize * TILE_MAP_SIZE + position.x as usize
+    }
+
+    pub fn tile_attributes(&self, position: Vec2) -> TileAttributes {
+        self.data.tile_attributes_data(self.map_area)[Self::index(position)].into()
+    }
+
     pub fn tile_data(&self, position: Vec2) -> TileData {
-        let id = self.data.tile_map_data(self.map_area)
-            [position.y as usize * TILE_MAP_SIZE + position.x as usize];
-        self.data.tile_data(self.data_area, id)
+        let id = self.data.tile_map_data(self.map_area)[Self::index(position)];
+        self.data.tile_data(
+            self.tile_attributes(position).bank_number,
+            self.data_area,
+            id,
+        )
     }
 
-    pub fn color_id(&self, position: Vec2) -> u8 {
-        self.tile_data(Vec2::new(position.x / 8, position.y / 8))
-            .color_id(position)
+    pub fn color_id(&self, position: Vec2) -> (u8, TileAttributes) {
+        let tile_position = Vec2::new(position.x / 8, position.y / 8);
+        let attributes = self.tile_attributes(tile_position);
+        let mut position_in_tile = Vec2::new(position.x % 8, position.y % 8);
+        if attributes.is_flipped_x {
+            position_in_tile.x = 7 - position_in_tile.x;
+        }
+        if attributes.is_flipped_y {
+            position_in_tile.y = 7 - position_in_tile.y;
+        }
+        (
+            self.tile_data(tile_position).color_id(position_in_tile),
+            attributes,
+        )
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Vram {
     data: VramData,
+    is_banked: bool,
+    bank_number: u8,
 }
 
 impl Vram {
+    pub fn new(is_banked: bool) -> Self {
+        Self {
+            data: Default::default(),
+            is_banked,
+            bank_number: 0,
+        }
+    }
+
     pub fn tile_map(&self, map_area: TileMapArea, data_area: TileDataArea) -> TileMap {
         TileMap {
             data: &self.data,
@@ -115,21 +182,55 @@ impl Vram {
         }
     }
 
-    pub fn tile_data(&self, area: TileDataArea, id: u8) -> TileData {
-        self.data.tile_data(area, id)
+    pub fn tile_data(&self, bank_number: u8, area: TileDataArea, id: u8) -> TileData {
+        self.data.tile_data(bank_number, area, id)
+    }
+
+    fn address(&self, address: u16) -> usize {
+        self.bank_number as usize * BANK_SIZE + address as usize
     }
 
     pub fn read(&self, address: u16) -> u8 {
-        *self.data.data.get(address as usize).unwrap_or_else(|| {
-            log::warn!("VRAM: Attempt to read from out of bounds: {:04X}", address);
-            &0xFF
-        })
+        *self
+            .data
+            .data
+            .get(self.address(address))
+            .unwrap_or_else(|| {
+                log::warn!("VRAM: Attempt to read from out of bounds: {:04X}", address);
+                &0xFF
+            })
     }
 
     pub fn write(&mut self, address: u16, value: u8) {
-        match self.data.data.get_mut(address as usize) {
+        let address = self.address(address);
+        match self.data.data.get_mut(address) {
             Some(e) => *e = value,
             None => log::warn!("VRAM: Attempt to write to out of bounds: {:04X}", address),
         }
     }
+
+    pub fn bank_bits(&self) -> u8 {
+        if self.is_banked {
+            0b1111_1110 | self.bank_number
+        } else {
+            0xFF
+        }
+    }
+
+    pub fn set_bank_bits(&mut self, value: u8) {
+        if self.is_banked {
+            self.bank_number = value & 0b1;
+        }
+    }
+
+    pub fn set_banked(&mut self, is_banked: bool) {
+        self.is_banked = is_banked;
+        if !is_banked {
+            self.bank_number = 0;
+        }
+    }
 }
+
+snapshot::fields!(VramData { data });
+
+snapshot::fields!(Vram { data, bank_number });
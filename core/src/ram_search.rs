@@ -0,0 +1,272 @@
+use crate::game_boy::GameBoy;
+use std::{fmt, str::FromStr};
+
+const ADDRESS_SPACE_SIZE: usize = 0x10000;
+
+const RAM_BANK_SIZE: usize = 0x2000;
+
+const RAM_ADDRESS: u16 = 0xA000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ValueType {
+    #[default]
+    Byte,
+    WordLittleEndian,
+    WordBigEndian,
+}
+
+impl ValueType {
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Byte => 1,
+            Self::WordLittleEndian | Self::WordBigEndian => 2,
+        }
+    }
+
+    fn mask(&self) -> u16 {
+        match self {
+            Self::Byte => 0xFF,
+            Self::WordLittleEndian | Self::WordBigEndian => 0xFFFF,
+        }
+    }
+
+    fn value(&self, bytes: &[u8]) -> u16 {
+        match (self, bytes) {
+            (Self::Byte, [value, ..]) => *value as u16,
+            (Self::WordLittleEndian, [low, high, ..]) => u16::from_le_bytes([*low, *high]),
+            (Self::WordBigEndian, [high, low, ..]) => u16::from_be_bytes([*high, *low]),
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Byte => write!(f, "8"),
+            Self::WordLittleEndian => write!(f, "16le"),
+            Self::WordBigEndian => write!(f, "16be"),
+        }
+    }
+}
+
+impl FromStr for ValueType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "8" => Ok(Self::Byte),
+            "16" | "16le" => Ok(Self::WordLittleEndian),
+            "16be" => Ok(Self::WordBigEndian),
+            _ => Err(format!("Invalid value type: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Filter {
+    EqualTo(u16),
+    NotEqualTo(u16),
+    LessThan(u16),
+    GreaterThan(u16),
+    Unchanged,
+    Changed,
+    Decreased,
+    Increased,
+    DecreasedBy(u16),
+    IncreasedBy(u16),
+}
+
+impl Filter {
+    fn matches(&self, previous: u16, current: u16, mask: u16) -> bool {
+        match *self {
+            Self::EqualTo(value) => current == value,
+            Self::NotEqualTo(value) => current != value,
+            Self::LessThan(value) => current < value,
+            Self::GreaterThan(value) => current > value,
+            Self::Unchanged => current == previous,
+            Self::Changed => current != previous,
+            Self::Decreased => current < previous,
+            Self::Increased => current > previous,
+            Self::DecreasedBy(value) => current == previous.wrapping_sub(value) & mask,
+            Self::IncreasedBy(value) => current == previous.wrapping_add(value) & mask,
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EqualTo(value) => write!(f, "={:X}", value),
+            Self::NotEqualTo(value) => write!(f, "!={:X}", value),
+            Self::LessThan(value) => write!(f, "<{:X}", value),
+            Self::GreaterThan(value) => write!(f, ">{:X}", value),
+            Self::Unchanged => write!(f, "="),
+            Self::Changed => write!(f, "!="),
+            Self::Decreased => write!(f, "<"),
+            Self::Increased => write!(f, ">"),
+            Self::DecreasedBy(value) => write!(f, "-{:X}", value),
+            Self::IncreasedBy(value) => write!(f, "+{:X}", value),
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| c.is_ascii_hexdigit() || c == '$')
+            .unwrap_or(s.len());
+        let (operator, value) = s.split_at(split);
+        let value = match value {
+            "" => None,
+            value => Some(
+                u16::from_str_radix(value.trim_start_matches('$'), 16)
+                    .map_err(|_| format!("Invalid filter: {}", s))?,
+            ),
+        };
+        Ok(match (operator.trim(), value) {
+            ("=", Some(value)) => Self::EqualTo(value),
+            ("!=", Some(value)) => Self::NotEqualTo(value),
+            ("<", Some(value)) => Self::LessThan(value),
+            (">", Some(value)) => Self::GreaterThan(value),
+            ("=", None) => Self::Unchanged,
+            ("!=", None) => Self::Changed,
+            ("<", None) => Self::Decreased,
+            (">", None) => Self::Increased,
+            ("-", Some(value)) => Self::DecreasedBy(value),
+            ("+", Some(value)) => Self::IncreasedBy(value),
+            _ => Err(format!("Invalid filter: {}", s))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RamAddress {
+    pub bank: Option<usize>,
+    pub address: u16,
+}
+
+impl RamAddress {
+    fn new(offset: usize) -> Self {
+        match offset.checked_sub(ADDRESS_SPACE_SIZE) {
+            Some(offset) => Self {
+                bank: Some(offset / RAM_BANK_SIZE),
+                address: RAM_ADDRESS + (offset % RAM_BANK_SIZE) as u16,
+            },
+            None => Self {
+                bank: None,
+                address: offset as u16,
+            },
+        }
+    }
+}
+
+impl fmt::Display for RamAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bank {
+            Some(bank) => write!(f, "{:02X}:{:04X}", bank, self.address),
+            None => write!(f, "{:04X}", self.address),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchResult {
+    pub address: RamAddress,
+    pub previous: u16,
+    pub current: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RamSearch {
+    value_type: ValueType,
+    snapshot: Vec<u8>,
+    candidates: Vec<usize>,
+}
+
+fn take_snapshot(game_boy: &mut GameBoy) -> Vec<u8> {
+    let mut snapshot = game_boy.dump();
+    snapshot.extend(game_boy.cartridge().ram());
+    snapshot
+}
+
+impl RamSearch {
+    pub fn new(game_boy: &mut GameBoy, value_type: ValueType) -> Self {
+        let snapshot = take_snapshot(game_boy);
+        let size = value_type.size();
+        let candidates = (0..snapshot.len())
+            .filter(|offset| {
+                let last = offset + size - 1;
+                last < snapshot.len()
+                    && (*offset < ADDRESS_SPACE_SIZE) == (last < ADDRESS_SPACE_SIZE)
+            })
+            .collect();
+        Self {
+            value_type,
+            snapshot,
+            candidates,
+        }
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    fn value(&self, data: &[u8], offset: usize) -> u16 {
+        self.value_type.value(&data[offset..])
+    }
+
+    pub fn filter(&mut self, game_boy: &mut GameBoy, filter: Filter) {
+        let snapshot = take_snapshot(game_boy);
+        if snapshot.len() != self.snapshot.len() {
+            log::warn!("RAM search: The memory size has changed");
+            return;
+        }
+        let mask = self.value_type.mask();
+        let candidates = std::mem::take(&mut self.candidates);
+        self.candidates = candidates
+            .into_iter()
+            .filter(|offset| {
+                filter.matches(
+                    self.value(&self.snapshot, *offset),
+                    self.value(&snapshot, *offset),
+                    mask,
+                )
+            })
+            .collect();
+        self.snapshot = snapshot;
+    }
+
+    pub fn results(&self, game_boy: &mut GameBoy, count: usize) -> Vec<SearchResult> {
+        let ram_size = game_boy.cartridge().ram().len();
+        self.candidates
+            .iter()
+            .take(count)
+            .map(|offset| {
+                let bytes: Vec<u8> = (*offset..*offset + self.value_type.size())
+                    .map(|offset| match offset.checked_sub(ADDRESS_SPACE_SIZE) {
+                        Some(offset) if offset < ram_size => game_boy.cartridge().ram()[offset],
+                        Some(_) => 0xFF,
+                        None => game_boy.read(offset as u16),
+                    })
+                    .collect();
+                SearchResult {
+                    address: RamAddress::new(*offset),
+                    previous: self.value(&self.snapshot, *offset),
+                    current: self.value_type.value(&bytes),
+                }
+            })
+            .collect()
+    }
+}
@@ -4,6 +4,12 @@ pub trait SerialConnection {
     fn receive(&mut self, bit: bool);
 
     fn send(&mut self) -> bool;
+
+    fn poll_clock(&mut self) -> bool {
+        false
+    }
+
+    fn tick(&mut self) {}
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -17,14 +23,21 @@ impl SerialConnection for NoSerialConnection {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReceivedByte {
+    pub value: u8,
+    pub m_cycle: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct BufferedSerialConnection {
     send_buffer: VecDeque<u8>,
     sending_data: u8,
     sent_bits: u8,
-    received_buffer: Vec<u8>,
+    received_buffer: Vec<ReceivedByte>,
     received_data: u8,
     received_bits: u8,
+    m_cycle: u64,
 }
 
 impl BufferedSerialConnection {
@@ -32,20 +45,35 @@ impl BufferedSerialConnection {
         self.send_buffer.extend(bytes);
     }
 
-    pub fn buffer(&self) -> &Vec<u8> {
+    pub fn buffer(&self) -> &Vec<ReceivedByte> {
         &self.received_buffer
     }
 
-    pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
+    pub fn buffer_mut(&mut self) -> &mut Vec<ReceivedByte> {
         &mut self.received_buffer
     }
+
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        self.received_buffer
+            .drain(..)
+            .map(|received_byte| received_byte.value)
+            .collect()
+    }
+
+    pub fn take_text(&mut self) -> String {
+        String::from_utf8_lossy(&self.take_bytes()).into()
+    }
 }
 
 impl SerialConnection for BufferedSerialConnection {
     fn receive(&mut self, bit: bool) {
-        self.received_data = self.received_data << 1 & (bit as u8);
+        self.received_data = self.received_data << 1 | (bit as u8);
         self.received_bits += 1;
         if self.received_bits >= 8 {
+            self.received_buffer.push(ReceivedByte {
+                value: self.received_data,
+                m_cycle: self.m_cycle,
+            });
             self.received_bits = 0;
         }
     }
@@ -62,4 +90,8 @@ impl SerialConnection for BufferedSerialConnection {
         }
         bit
     }
+
+    fn tick(&mut self) {
+        self.m_cycle += 1;
+    }
 }
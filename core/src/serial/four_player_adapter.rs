@@ -0,0 +1,197 @@
+use crate::game_boy::GameBoy;
+
+pub const MAX_PLAYERS: usize = 4;
+
+const BIT_M_CYCLES: u64 = 128;
+
+const PING_PACKET_SIZE: usize = 4;
+
+const PING_HEADER: u8 = 0xFE;
+
+const ACK: u8 = 0x88;
+
+const START: u8 = 0xAA;
+
+const TRANSITION: u8 = 0xCC;
+
+const RESTART: u8 = 0xFF;
+
+const RESTART_COUNT: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+enum Phase {
+    #[default]
+    Ping,
+    Transition,
+    Transmission,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FourPlayerAdapter {
+    phase: Phase,
+    byte_index: usize,
+    sent_bits: u8,
+    rest_m_cycles: u64,
+    sending_data: [u8; MAX_PLAYERS],
+    received_data: [u8; MAX_PLAYERS],
+    ping_responses: [[u8; PING_PACKET_SIZE]; MAX_PLAYERS],
+    connected_players: u8,
+    rate: u8,
+    packet_size: usize,
+    sending_packet: Vec<u8>,
+    receiving_packet: Vec<u8>,
+    restart_count: usize,
+}
+
+impl Default for FourPlayerAdapter {
+    fn default() -> Self {
+        Self {
+            phase: Phase::Ping,
+            byte_index: 0,
+            sent_bits: 0,
+            rest_m_cycles: BIT_M_CYCLES,
+            sending_data: [0; MAX_PLAYERS],
+            received_data: [0; MAX_PLAYERS],
+            ping_responses: [[0; PING_PACKET_SIZE]; MAX_PLAYERS],
+            connected_players: 0,
+            rate: 0,
+            packet_size: 1,
+            sending_packet: vec![],
+            receiving_packet: vec![],
+            restart_count: 0,
+        }
+    }
+}
+
+impl FourPlayerAdapter {
+    pub fn is_transmitting(&self) -> bool {
+        self.phase == Phase::Transmission
+    }
+
+    pub fn is_connected(&self, player: usize) -> bool {
+        player < MAX_PLAYERS && self.connected_players >> player & 1 != 0
+    }
+
+    pub fn rate(&self) -> u8 {
+        self.rate
+    }
+
+    pub fn packet_size(&self) -> usize {
+        self.packet_size
+    }
+
+    fn byte_interval(&self) -> u64 {
+        ((self.rate & 0x0F) as u64 * 12 + 0xA0) / 4
+    }
+
+    fn outgoing_byte(&self, player: usize) -> u8 {
+        match self.phase {
+            Phase::Ping if self.byte_index == 0 => PING_HEADER,
+            Phase::Ping => self.connected_players << 4 | (player as u8 + 1),
+            Phase::Transition => TRANSITION,
+            Phase::Transmission => self
+                .sending_packet
+                .get(self.byte_index)
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    fn finish_ping_packet(&mut self) {
+        let player1 = self.ping_responses[0];
+        if player1.iter().all(|response| *response == START) {
+            self.phase = Phase::Transition;
+            return;
+        }
+        self.connected_players = 0;
+        for (player, responses) in self.ping_responses.iter().enumerate() {
+            if responses[..2] == [ACK, ACK] {
+                self.connected_players |= 1 << player;
+            }
+        }
+        if self.is_connected(0) {
+            self.rate = player1[2];
+            self.packet_size = std::cmp::max(player1[3] as usize, 1);
+        }
+    }
+
+    fn start_transmission(&mut self) {
+        self.phase = Phase::Transmission;
+        self.sending_packet = vec![0; self.packet_size * MAX_PLAYERS];
+        self.receiving_packet = vec![0; self.packet_size * MAX_PLAYERS];
+        self.restart_count = 0;
+    }
+
+    fn complete_byte(&mut self) {
+        match self.phase {
+            Phase::Ping => {
+                for (player, responses) in self.ping_responses.iter_mut().enumerate() {
+                    responses[self.byte_index] = self.received_data[player];
+                }
+                self.byte_index += 1;
+                if self.byte_index == PING_PACKET_SIZE {
+                    self.byte_index = 0;
+                    self.finish_ping_packet();
+                }
+            }
+            Phase::Transition => {
+                self.byte_index += 1;
+                if self.byte_index == PING_PACKET_SIZE {
+                    self.byte_index = 0;
+                    self.start_transmission();
+                }
+            }
+            Phase::Transmission => {
+                if self.byte_index < self.packet_size {
+                    for player in 0..MAX_PLAYERS {
+                        self.receiving_packet[player * self.packet_size + self.byte_index] =
+                            if self.is_connected(player) {
+                                self.received_data[player]
+                            } else {
+                                0
+                            };
+                    }
+                }
+                self.restart_count = match self.received_data[0] {
+                    RESTART => self.restart_count + 1,
+                    _ => 0,
+                };
+                self.byte_index += 1;
+                if self.restart_count >= RESTART_COUNT {
+                    self.phase = Phase::Ping;
+                    self.byte_index = 0;
+                } else if self.byte_index == self.sending_packet.len() {
+                    self.byte_index = 0;
+                    std::mem::swap(&mut self.sending_packet, &mut self.receiving_packet);
+                }
+            }
+        }
+    }
+
+    pub fn tick(&mut self, game_boys: &mut [GameBoy]) {
+        self.rest_m_cycles = self.rest_m_cycles.saturating_sub(1);
+        if self.rest_m_cycles > 0 {
+            return;
+        }
+        if self.sent_bits == 0 {
+            for player in 0..MAX_PLAYERS {
+                self.sending_data[player] = self.outgoing_byte(player);
+            }
+        }
+        for player in 0..MAX_PLAYERS {
+            let output = self.sending_data[player] >> 7 != 0;
+            self.sending_data[player] <<= 1;
+            let input = game_boys
+                .get_mut(player)
+                .is_none_or(|game_boy| game_boy.exchange_serial_bit(output));
+            self.received_data[player] = self.received_data[player] << 1 | input as u8;
+        }
+        self.sent_bits += 1;
+        self.rest_m_cycles = BIT_M_CYCLES;
+        if self.sent_bits == 8 {
+            self.sent_bits = 0;
+            self.rest_m_cycles += self.byte_interval();
+            self.complete_byte();
+        }
+    }
+}
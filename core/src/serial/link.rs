@@ -0,0 +1,56 @@
+use super::SerialConnection;
+use crate::game_boy::GameBoy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct BitExchange {
+    input: bool,
+    output: bool,
+}
+
+impl BitExchange {
+    pub fn new(input: bool) -> Self {
+        Self {
+            input,
+            output: true,
+        }
+    }
+
+    pub fn output(&self) -> bool {
+        self.output
+    }
+}
+
+impl SerialConnection for BitExchange {
+    fn receive(&mut self, bit: bool) {
+        self.output = bit;
+    }
+
+    fn send(&mut self) -> bool {
+        self.input
+    }
+}
+
+#[derive(Debug)]
+pub struct LinkPort<'a> {
+    peer: &'a mut GameBoy,
+    received_bit: bool,
+}
+
+impl<'a> LinkPort<'a> {
+    pub fn new(peer: &'a mut GameBoy) -> Self {
+        Self {
+            peer,
+            received_bit: true,
+        }
+    }
+}
+
+impl SerialConnection for LinkPort<'_> {
+    fn receive(&mut self, bit: bool) {
+        self.received_bit = self.peer.exchange_serial_bit(bit);
+    }
+
+    fn send(&mut self) -> bool {
+        self.received_bit
+    }
+}
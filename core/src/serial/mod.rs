@@ -1,9 +1,21 @@
 pub mod connection;
+mod four_player_adapter;
+mod link;
+mod printer;
+#[cfg(not(target_arch = "wasm32"))]
+mod socket;
 
 pub use connection::*;
+pub use four_player_adapter::{FourPlayerAdapter, MAX_PLAYERS};
+pub(crate) use link::BitExchange;
+pub use link::LinkPort;
+pub use printer::{PrintedImage, Printer};
+#[cfg(not(target_arch = "wasm32"))]
+pub use socket::SocketConnection;
 
 use crate::{
     interrupt::{Interrupt, InterruptController},
+    snapshot,
     util::bits::Bits,
 };
 
@@ -16,10 +28,11 @@ pub struct Serial {
     uses_internal_clock: bool,
     transfered_bits: u8,
     rest_cycles: u64,
+    is_cgb_mode: bool,
 }
 
-impl Default for Serial {
-    fn default() -> Self {
+impl Serial {
+    pub fn new(is_cgb_mode: bool) -> Self {
         Self {
             buffer: 0,
             is_started: false,
@@ -28,14 +41,13 @@ impl Default for Serial {
             uses_internal_clock: false,
             transfered_bits: 0,
             rest_cycles: 0,
+            is_cgb_mode,
         }
     }
-}
 
-impl Serial {
     fn cycles(&self) -> u64 {
-        if self.is_fast {
-            128 // CGB: 4
+        if self.is_cgb_mode && self.is_fast {
+            4
         } else {
             128
         }
@@ -49,10 +61,10 @@ impl Serial {
     }
 
     fn transfer(&mut self, connection: &mut impl SerialConnection) {
-        let input = connection.send();
         let output = self.buffer.bit(7);
-        self.buffer = self.buffer << 1 | (input as u8);
         connection.receive(output);
+        let input = connection.send();
+        self.buffer = self.buffer << 1 | (input as u8);
         self.transfered_bits += 1;
         if self.transfered_bits >= 8 {
             self.is_started = false;
@@ -65,6 +77,10 @@ impl Serial {
         interrupt_controller: &mut InterruptController,
         connection: &mut impl SerialConnection,
     ) {
+        connection.tick();
+        if connection.poll_clock() {
+            self.receive(connection);
+        }
         self.rest_cycles = self.rest_cycles.saturating_sub(1);
         if self.rest_cycles > 0 {
             return;
@@ -74,11 +90,15 @@ impl Serial {
             self.is_completed = false;
             interrupt_controller.request(Interrupt::Serial);
         }
-        if self.is_started {
+        if self.is_started && self.uses_internal_clock {
             self.transfer(connection);
         }
     }
 
+    pub fn set_cgb_mode(&mut self, is_cgb_mode: bool) {
+        self.is_cgb_mode = is_cgb_mode;
+    }
+
     pub fn data(&self) -> u8 {
         self.buffer
     }
@@ -90,7 +110,7 @@ impl Serial {
     pub fn control_bits(&self) -> u8 {
         0b0111_1100
             | (self.is_started as u8) << 7
-            | /* CGB: (self.is_fast as u8) */ 0b1 << 1
+            | ((!self.is_cgb_mode || self.is_fast) as u8) << 1
             | self.uses_internal_clock as u8
     }
 
@@ -103,11 +123,24 @@ impl Serial {
     }
 
     pub fn receive(&mut self, connection: &mut impl SerialConnection) {
-        if !self.uses_internal_clock {
-            if !self.is_started {
-                self.start();
-            }
-            self.transfer(connection);
+        if self.uses_internal_clock {
+            connection.receive(true);
+            connection.send();
+            return;
         }
+        if !self.is_started {
+            self.start();
+        }
+        self.transfer(connection);
     }
 }
+
+snapshot::fields!(Serial {
+    buffer,
+    is_started,
+    is_completed,
+    is_fast,
+    uses_internal_clock,
+    transfered_bits,
+    rest_cycles,
+});
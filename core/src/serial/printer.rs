@@ -0,0 +1,271 @@
+use super::SerialConnection;
+use crate::ppu::Color;
+
+const MAGIC_BYTES: [u8; 2] = [0x88, 0x33];
+
+const ALIVE: u8 = 0x81;
+
+const BUFFER_SIZE: usize = 0x2000;
+
+const TILE_SIZE: usize = 16;
+
+const TILES_PER_LINE: usize = 20;
+
+const DEFAULT_PALETTE: u8 = 0xE4;
+
+const BUSY_STATUS_COUNT: u8 = 4;
+
+mod command {
+    pub const INIT: u8 = 0x01;
+    pub const PRINT: u8 = 0x02;
+    pub const DATA: u8 = 0x04;
+    pub const BREAK: u8 = 0x08;
+    pub const STATUS: u8 = 0x0F;
+}
+
+mod status {
+    pub const CHECKSUM_ERROR: u8 = 0x01;
+    pub const PRINTING: u8 = 0x02;
+    pub const IMAGE_DATA_FULL: u8 = 0x04;
+    pub const UNPROCESSED_DATA: u8 = 0x08;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+enum Phase {
+    #[default]
+    Magic,
+    Command,
+    Compression,
+    Length,
+    Data,
+    Checksum,
+    Alive,
+    Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct Packet {
+    command: u8,
+    is_compressed: bool,
+    length: u16,
+    data: Vec<u8>,
+    checksum: u16,
+    received_checksum: u16,
+    received_bytes: usize,
+}
+
+impl Packet {
+    fn payload(&self) -> Vec<u8> {
+        if !self.is_compressed {
+            return self.data.clone();
+        }
+        let mut payload = vec![];
+        let mut bytes = self.data.iter();
+        while let Some(control) = bytes.next() {
+            if control & 0x80 != 0 {
+                let value = bytes.next().copied().unwrap_or_default();
+                payload.extend(std::iter::repeat_n(value, (control & 0x7F) as usize + 2));
+            } else {
+                payload.extend(bytes.by_ref().take(*control as usize + 1));
+            }
+        }
+        payload
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintedImage {
+    pixels: Vec<Color>,
+}
+
+impl PrintedImage {
+    pub const WIDTH: usize = TILES_PER_LINE * 8;
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / Self::WIDTH
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Printer {
+    received_data: u8,
+    received_bits: u8,
+    sending_data: u8,
+    next_sending_data: u8,
+    sent_bits: u8,
+    phase: Phase,
+    packet: Packet,
+    buffer: Vec<u8>,
+    status: u8,
+    busy_count: u8,
+    page: Option<PrintedImage>,
+    images: Vec<PrintedImage>,
+}
+
+impl Printer {
+    pub fn take_images(&mut self) -> Vec<PrintedImage> {
+        std::mem::take(&mut self.images)
+    }
+
+    pub fn finish_page(&mut self) {
+        if let Some(page) = self.page.take() {
+            self.images.push(page);
+        }
+    }
+
+    fn print(&mut self, margins: u8, palette: u8) {
+        let palette = match palette {
+            0 => DEFAULT_PALETTE,
+            palette => palette,
+        };
+        let rows = self.buffer.len() / (TILE_SIZE * TILES_PER_LINE);
+        let page = self
+            .page
+            .get_or_insert_with(|| PrintedImage { pixels: vec![] });
+        for y in 0..rows * 8 {
+            for x in 0..PrintedImage::WIDTH {
+                let tile = (y / 8 * TILES_PER_LINE + x / 8) * TILE_SIZE;
+                let low = self.buffer[tile + y % 8 * 2] >> (7 - x % 8) & 1;
+                let high = self.buffer[tile + y % 8 * 2 + 1] >> (7 - x % 8) & 1;
+                let index = high << 1 | low;
+                page.pixels.push(Color::from(palette >> (index * 2)));
+            }
+        }
+        self.buffer.clear();
+        if margins & 0x0F != 0 {
+            self.finish_page();
+        }
+    }
+
+    fn execute(&mut self) {
+        if self.packet.checksum != self.packet.received_checksum {
+            self.status |= status::CHECKSUM_ERROR;
+            return;
+        }
+        self.status &= !status::CHECKSUM_ERROR;
+        match self.packet.command {
+            command::INIT | command::BREAK => {
+                self.buffer.clear();
+                self.status = 0;
+                self.busy_count = 0;
+            }
+            command::PRINT => {
+                let data = &self.packet.data;
+                if let [_, margins, palette, ..] = data[..] {
+                    self.print(margins, palette);
+                }
+                self.status = status::PRINTING | status::IMAGE_DATA_FULL;
+                self.busy_count = BUSY_STATUS_COUNT;
+            }
+            command::DATA => {
+                let payload = self.packet.payload();
+                let length = std::cmp::min(payload.len(), BUFFER_SIZE - self.buffer.len());
+                self.buffer.extend(&payload[..length]);
+                if !self.buffer.is_empty() {
+                    self.status |= status::UNPROCESSED_DATA;
+                }
+            }
+            command::STATUS => {
+                if self.busy_count > 0 {
+                    self.busy_count -= 1;
+                    if self.busy_count == 0 {
+                        self.status &= !status::PRINTING;
+                    }
+                }
+            }
+            command => log::warn!("Unknown printer command: {:02X}", command),
+        }
+    }
+
+    fn process(&mut self, value: u8) {
+        let packet = &mut self.packet;
+        self.next_sending_data = 0x00;
+        match self.phase {
+            Phase::Magic => {
+                packet.received_bytes = match packet.received_bytes {
+                    _ if value == MAGIC_BYTES[0] => 1,
+                    1 if value == MAGIC_BYTES[1] => 2,
+                    _ => 0,
+                };
+                if packet.received_bytes == MAGIC_BYTES.len() {
+                    *packet = Packet::default();
+                    self.phase = Phase::Command;
+                }
+            }
+            Phase::Command => {
+                packet.command = value;
+                packet.checksum = value as u16;
+                self.phase = Phase::Compression;
+            }
+            Phase::Compression => {
+                packet.is_compressed = value & 1 != 0;
+                packet.checksum = packet.checksum.wrapping_add(value as u16);
+                self.phase = Phase::Length;
+            }
+            Phase::Length => {
+                packet.length |= (value as u16) << (packet.received_bytes * 8);
+                packet.checksum = packet.checksum.wrapping_add(value as u16);
+                packet.received_bytes += 1;
+                if packet.received_bytes == 2 {
+                    packet.received_bytes = 0;
+                    self.phase = match packet.length {
+                        0 => Phase::Checksum,
+                        _ => Phase::Data,
+                    };
+                }
+            }
+            Phase::Data => {
+                packet.data.push(value);
+                packet.checksum = packet.checksum.wrapping_add(value as u16);
+                if packet.data.len() == packet.length as usize {
+                    self.phase = Phase::Checksum;
+                }
+            }
+            Phase::Checksum => {
+                packet.received_checksum |= (value as u16) << (packet.received_bytes * 8);
+                packet.received_bytes += 1;
+                if packet.received_bytes == 2 {
+                    self.execute();
+                    self.next_sending_data = ALIVE;
+                    self.phase = Phase::Alive;
+                }
+            }
+            Phase::Alive => {
+                self.next_sending_data = self.status;
+                self.phase = Phase::Status;
+            }
+            Phase::Status => {
+                self.packet = Packet::default();
+                self.phase = Phase::Magic;
+            }
+        }
+    }
+}
+
+impl SerialConnection for Printer {
+    fn receive(&mut self, bit: bool) {
+        self.received_data = self.received_data << 1 | (bit as u8);
+        self.received_bits += 1;
+        if self.received_bits >= 8 {
+            self.received_bits = 0;
+            self.process(self.received_data);
+        }
+    }
+
+    fn send(&mut self) -> bool {
+        if self.sent_bits == 0 {
+            self.sending_data = self.next_sending_data;
+        }
+        let bit = self.sending_data >> 7 != 0;
+        self.sending_data <<= 1;
+        self.sent_bits += 1;
+        if self.sent_bits >= 8 {
+            self.sent_bits = 0;
+        }
+        bit
+    }
+}
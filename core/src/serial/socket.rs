@@ -0,0 +1,163 @@
+use super::SerialConnection;
+use std::{
+    fmt,
+    io::{self, BufReader, Read, Write},
+    net::TcpStream,
+    sync::mpsc::{self, RecvTimeoutError, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+const CLOCK: u8 = 0x00;
+
+const REPLY: u8 = 0x02;
+
+const SEQUENCE_SHIFT: u8 = 2;
+
+const REPLY_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub struct SocketConnection {
+    writer: Box<dyn Write>,
+    messages: mpsc::Receiver<u8>,
+    clock: Option<u8>,
+    sequence: u8,
+    received_bit: bool,
+    bit_index: u8,
+    skipped_bits: u8,
+    is_connected: bool,
+}
+
+impl fmt::Debug for SocketConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketConnection")
+            .field("clock", &self.clock)
+            .field("sequence", &self.sequence)
+            .field("received_bit", &self.received_bit)
+            .field("bit_index", &self.bit_index)
+            .field("skipped_bits", &self.skipped_bits)
+            .field("is_connected", &self.is_connected)
+            .finish()
+    }
+}
+
+impl SocketConnection {
+    pub fn new(reader: impl Read + Send + 'static, writer: impl Write + 'static) -> Self {
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            for message in BufReader::new(reader).bytes() {
+                match message {
+                    Ok(message) if sender.send(message).is_ok() => {}
+                    _ => break,
+                }
+            }
+        });
+        Self {
+            writer: Box::new(writer),
+            messages,
+            clock: None,
+            sequence: 0,
+            received_bit: true,
+            bit_index: 0,
+            skipped_bits: 0,
+            is_connected: true,
+        }
+    }
+
+    pub fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream.try_clone()?, stream))
+    }
+
+    #[cfg(unix)]
+    pub fn from_unix(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
+        Ok(Self::new(stream.try_clone()?, stream))
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.is_connected
+    }
+
+    fn disconnect(&mut self, reason: &str) {
+        if self.is_connected {
+            log::warn!("The link is disconnected: {}", reason);
+            self.is_connected = false;
+            self.clock = None;
+        }
+    }
+
+    fn write(&mut self, message: u8) {
+        if let Err(error) = self
+            .writer
+            .write_all(&[message])
+            .and_then(|_| self.writer.flush())
+        {
+            self.disconnect(&format!("could not write: {:?}", error));
+        }
+    }
+
+    fn wait_reply(&mut self) -> Option<bool> {
+        let deadline = Instant::now() + REPLY_TIMEOUT;
+        while self.is_connected {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match self.messages.recv_timeout(timeout) {
+                Ok(message) if message & REPLY == 0 => self.write(message | REPLY | 1),
+                Ok(message) if message >> SEQUENCE_SHIFT == self.sequence => {
+                    return Some(message & 1 != 0)
+                }
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    log::warn!("The link peer did not reply, skipping the rest of the byte");
+                    self.skipped_bits = 7 - self.bit_index;
+                    return None;
+                }
+                Err(RecvTimeoutError::Disconnected) => self.disconnect("the peer closed the link"),
+            }
+        }
+        None
+    }
+}
+
+impl SerialConnection for SocketConnection {
+    fn receive(&mut self, bit: bool) {
+        if !self.is_connected {
+            self.received_bit = true;
+            return;
+        }
+        match self.clock.take() {
+            Some(clock) => {
+                self.write(clock & !1 | REPLY | bit as u8);
+                self.received_bit = clock & 1 != 0;
+            }
+            None if self.skipped_bits > 0 => {
+                self.skipped_bits -= 1;
+                self.bit_index = (self.bit_index + 1) % 8;
+                self.received_bit = true;
+            }
+            None => {
+                self.sequence = (self.sequence + 1) & (u8::MAX >> SEQUENCE_SHIFT);
+                self.write(self.sequence << SEQUENCE_SHIFT | CLOCK | bit as u8);
+                self.received_bit = self.wait_reply().unwrap_or(true);
+                self.bit_index = (self.bit_index + 1) % 8;
+            }
+        }
+    }
+
+    fn send(&mut self) -> bool {
+        self.received_bit
+    }
+
+    fn poll_clock(&mut self) -> bool {
+        while self.is_connected {
+            match self.messages.try_recv() {
+                Ok(message) if message & REPLY == 0 => {
+                    self.clock = Some(message);
+                    return true;
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.disconnect("the peer closed the link"),
+            }
+        }
+        false
+    }
+}
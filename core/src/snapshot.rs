@@ -0,0 +1,184 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    CartridgeMismatch,
+    ModelMismatch,
+    UnexpectedEnd,
+    InvalidValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn read(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < length {
+            return Err(Error::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(length);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+pub trait Snapshot {
+    fn save(&self, writer: &mut Writer);
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error>;
+}
+
+macro_rules! integer {
+    ($type: ty) => {
+        impl Snapshot for $type {
+            fn save(&self, writer: &mut Writer) {
+                writer.write(&self.to_le_bytes());
+            }
+
+            fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+                let bytes = reader.read(std::mem::size_of::<$type>())?;
+                *self = <$type>::from_le_bytes(bytes.try_into().unwrap());
+                Ok(())
+            }
+        }
+    };
+}
+
+integer!(u8);
+
+integer!(u16);
+
+integer!(u32);
+
+integer!(u64);
+
+integer!(i64);
+
+impl Snapshot for usize {
+    fn save(&self, writer: &mut Writer) {
+        (*self as u64).save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        let mut value = 0u64;
+        value.load(reader)?;
+        *self = usize::try_from(value).map_err(|_| Error::InvalidValue)?;
+        Ok(())
+    }
+}
+
+impl Snapshot for bool {
+    fn save(&self, writer: &mut Writer) {
+        (*self as u8).save(writer);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        let mut value = 0u8;
+        value.load(reader)?;
+        *self = match value {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::InvalidValue),
+        };
+        Ok(())
+    }
+}
+
+impl Snapshot for Vec<u8> {
+    fn save(&self, writer: &mut Writer) {
+        self.len().save(writer);
+        writer.write(self);
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        let mut length = 0usize;
+        length.load(reader)?;
+        if length != self.len() {
+            return Err(Error::InvalidValue);
+        }
+        self.copy_from_slice(reader.read(length)?);
+        Ok(())
+    }
+}
+
+impl<T: Snapshot, const N: usize> Snapshot for [T; N] {
+    fn save(&self, writer: &mut Writer) {
+        for element in self.iter() {
+            element.save(writer);
+        }
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        for element in self.iter_mut() {
+            element.load(reader)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Snapshot + Default> Snapshot for Option<T> {
+    fn save(&self, writer: &mut Writer) {
+        self.is_some().save(writer);
+        if let Some(value) = self {
+            value.save(writer);
+        }
+    }
+
+    fn load(&mut self, reader: &mut Reader) -> Result<(), Error> {
+        let mut is_some = false;
+        is_some.load(reader)?;
+        *self = if is_some {
+            let mut value = T::default();
+            value.load(reader)?;
+            Some(value)
+        } else {
+            None
+        };
+        Ok(())
+    }
+}
+
+macro_rules! fields {
+    ($type: ty { $($field: ident),* $(,)? }) => {
+        impl $crate::snapshot::Snapshot for $type {
+            fn save(&self, writer: &mut $crate::snapshot::Writer) {
+                $($crate::snapshot::Snapshot::save(&self.$field, writer);)*
+            }
+
+            fn load(
+                &mut self,
+                reader: &mut $crate::snapshot::Reader,
+            ) -> Result<(), $crate::snapshot::Error> {
+                $($crate::snapshot::Snapshot::load(&mut self.$field, reader)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use fields;
@@ -0,0 +1,59 @@
+use crate::{snapshot, util::bits::Bits};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Speed {
+    is_switchable: bool,
+    is_double: bool,
+    is_prepared: bool,
+}
+
+impl Speed {
+    pub fn new(is_switchable: bool) -> Self {
+        Self {
+            is_switchable,
+            is_double: false,
+            is_prepared: false,
+        }
+    }
+
+    pub fn set_switchable(&mut self, is_switchable: bool) {
+        self.is_switchable = is_switchable;
+    }
+
+    pub fn m_cycles_per_tick(&self) -> u64 {
+        if self.is_double {
+            2
+        } else {
+            1
+        }
+    }
+
+    pub fn bits(&self) -> u8 {
+        if self.is_switchable {
+            (self.is_double as u8) << 7 | 0b0111_1110 | self.is_prepared as u8
+        } else {
+            0xFF
+        }
+    }
+
+    pub fn set_bits(&mut self, value: u8) {
+        if self.is_switchable {
+            self.is_prepared = value.bit(0);
+        }
+    }
+
+    pub fn switch(&mut self) -> bool {
+        if self.is_switchable && self.is_prepared {
+            self.is_double = !self.is_double;
+            self.is_prepared = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+snapshot::fields!(Speed {
+    is_double,
+    is_prepared,
+});
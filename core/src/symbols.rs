@@ -0,0 +1,116 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Error {
+    InvalidLine(usize, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidLine(line, text) => {
+                write!(f, "Invalid symbol at line {}: {}", line, text)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Symbols {
+    labels: BTreeMap<(usize, u16), String>,
+    addresses: BTreeMap<String, (usize, u16)>,
+    banks: BTreeSet<usize>,
+}
+
+fn region_start(address: u16) -> u16 {
+    match address {
+        0x0000..=0x3FFF => 0x0000,
+        0x4000..=0x7FFF => 0x4000,
+        0x8000..=0x9FFF => 0x8000,
+        0xA000..=0xBFFF => 0xA000,
+        0xC000..=0xCFFF => 0xC000,
+        0xD000..=0xDFFF => 0xD000,
+        0xE000..=0xFF7F => address,
+        0xFF80..=0xFFFF => 0xFF80,
+    }
+}
+
+impl Symbols {
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn insert(&mut self, bank: usize, address: u16, label: String) {
+        self.addresses.insert(label.clone(), (bank, address));
+        self.labels.insert((bank, address), label);
+        self.banks.insert(bank);
+    }
+
+    pub fn address(&self, label: &str) -> Option<(usize, u16)> {
+        self.addresses.get(label).copied()
+    }
+
+    fn nearest(&self, bank: usize, address: u16) -> Option<(u16, &str)> {
+        self.labels
+            .range((bank, region_start(address))..=(bank, address))
+            .next_back()
+            .map(|((_, address), label)| (*address, label.as_str()))
+    }
+
+    pub fn label(&self, bank: Option<usize>, address: u16) -> Option<&str> {
+        match bank {
+            Some(bank) => self.labels.get(&(bank, address)).map(String::as_str),
+            None => self
+                .banks
+                .iter()
+                .find_map(|bank| self.labels.get(&(*bank, address)))
+                .map(String::as_str),
+        }
+    }
+
+    pub fn describe(&self, bank: Option<usize>, address: u16) -> Option<String> {
+        let (label_address, label) = match bank {
+            Some(bank) => self.nearest(bank, address)?,
+            None => self
+                .banks
+                .iter()
+                .filter_map(|bank| self.nearest(*bank, address))
+                .max_by_key(|(address, _)| *address)?,
+        };
+        Some(match address - label_address {
+            0 => label.into(),
+            offset => format!("{}+${:X}", label, offset),
+        })
+    }
+}
+
+impl FromStr for Symbols {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut symbols = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split(';').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = || Error::InvalidLine(index + 1, line.into());
+            let (location, label) = line.split_once(char::is_whitespace).ok_or_else(error)?;
+            let (bank, address) = location.split_once(':').ok_or_else(error)?;
+            symbols.insert(
+                usize::from_str_radix(bank, 16).map_err(|_| error())?,
+                u16::from_str_radix(address, 16).map_err(|_| error())?,
+                label.trim().into(),
+            );
+        }
+        Ok(symbols)
+    }
+}
@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Tilt {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Tilt {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            x: x.clamp(-1.0, 1.0),
+            y: y.clamp(-1.0, 1.0),
+        }
+    }
+}
+
+impl FromStr for Tilt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_tilt = || format!("Invalid tilt: {}", s);
+        let (x, y) = s.split_once(',').ok_or_else(invalid_tilt)?;
+        let parse = |value: &str| value.trim().parse::<f32>().map_err(|_| invalid_tilt());
+        Ok(Self::new(parse(x)?, parse(y)?))
+    }
+}
+
+pub trait TiltSource {
+    fn tilt(&mut self, frame: u64) -> Tilt;
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TiltScript {
+    events: Vec<(u64, Tilt)>,
+}
+
+impl TiltSource for TiltScript {
+    fn tilt(&mut self, frame: u64) -> Tilt {
+        self.events
+            .iter()
+            .take_while(|(event_frame, _)| *event_frame <= frame)
+            .last()
+            .map(|(_, tilt)| *tilt)
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for TiltScript {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut events = vec![];
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid_line = || format!("Invalid tilt script at line {}: {}", index + 1, line);
+            let (frame, tilt) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(invalid_line)?;
+            events.push((
+                frame.parse().map_err(|_| invalid_line())?,
+                tilt.parse().map_err(|_| invalid_line())?,
+            ));
+        }
+        events.sort_by_key(|(frame, _)| *frame);
+        Ok(Self { events })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::util::bits::Bits;
+use crate::{snapshot, util::bits::Bits};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct InputClock {
@@ -42,3 +42,8 @@ impl Control {
         self.input_clock_index = value & 0b11;
     }
 }
+
+snapshot::fields!(Control {
+    is_enabled,
+    input_clock_index,
+});
@@ -1,15 +1,15 @@
+use crate::snapshot;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Divider {
     counter: u16,
 }
 
-impl Default for Divider {
-    fn default() -> Self {
-        Self { counter: 0xAB00 }
+impl Divider {
+    pub fn new(counter: u16) -> Self {
+        Self { counter }
     }
-}
 
-impl Divider {
     pub fn counter(&self) -> u16 {
         self.counter
     }
@@ -26,3 +26,5 @@ impl Divider {
         self.counter = 0;
     }
 }
+
+snapshot::fields!(Divider { counter });
@@ -4,7 +4,10 @@ mod divider;
 use control::Control;
 pub use divider::Divider;
 
-use crate::interrupt::{Interrupt, InterruptController};
+use crate::{
+    interrupt::{Interrupt, InterruptController},
+    snapshot,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Timer {
@@ -62,3 +65,12 @@ impl Timer {
         self.control.set_bits(value);
     }
 }
+
+snapshot::fields!(Timer {
+    control,
+    counter,
+    modulo,
+    previous_output,
+    overflow,
+    is_reloading,
+});
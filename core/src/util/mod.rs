@@ -1,2 +1,3 @@
 pub mod ascii;
 pub mod bits;
+pub mod crc32;
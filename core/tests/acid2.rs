@@ -0,0 +1,7 @@
+mod harness;
+
+#[test]
+#[ignore = "needs the test ROMs, see UUGB_TEST_ROMS"]
+fn dmg_acid2() {
+    harness::run_screenshot("dmg-acid2/dmg-acid2.gb", "dmg-acid2/reference-dmg.png");
+}
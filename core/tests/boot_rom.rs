@@ -0,0 +1,102 @@
+mod harness;
+
+use core::{BootRom, Color, GameBoy, Renderer, Rgb, Vec2};
+
+const M_CYCLES_PER_FRAME: u64 = 17_556;
+
+const RED: Color = Color::Rgb(Rgb {
+    red: 0x1F,
+    green: 0x00,
+    blue: 0x00,
+});
+
+#[derive(Debug, Default)]
+struct FirstPixel(Option<Color>);
+
+impl Renderer for FirstPixel {
+    fn render(&mut self, position: Vec2, color: Color) {
+        if position == Vec2::new(0, 0) {
+            self.0 = Some(color);
+        }
+    }
+}
+
+fn cgb_boot_rom(compatibility_bits: u8) -> BootRom {
+    let mut data = vec![0x00; 0x900];
+    data[..15].copy_from_slice(&[
+        0x3E, 0x80, // LD A,$80
+        0xE0, 0x68, // LDH (BCPS),A
+        0x3E, 0x1F, // LD A,$1F
+        0xE0, 0x69, // LDH (BCPD),A
+        0xAF, // XOR A
+        0xE0, 0x69, // LDH (BCPD),A
+        0x3E, 0x00, // LD A,compatibility_bits
+        0xE0, 0x4C, // LDH (KEY0),A
+    ]);
+    data[12] = compatibility_bits;
+    data[0xFC..0x100].copy_from_slice(&[
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0x50, // LDH (BOOT),A
+    ]);
+    BootRom::new(data).unwrap()
+}
+
+fn game_boy(compatibility_bits: u8) -> GameBoy {
+    let cartridge = harness::test_rom(&[
+        0x3E, 0x04, // LD A,$04
+        0xE0, 0x4C, // LDH (KEY0),A
+        0x3E, 0x91, // LD A,$91
+        0xE0, 0x40, // LDH (LCDC),A
+        0x18, 0xFE, // JR -2
+    ])
+    .cartridge();
+    GameBoy::builder(cartridge)
+        .boot_rom(cgb_boot_rom(compatibility_bits))
+        .build()
+}
+
+fn run_frames(game_boy: &mut GameBoy, frames: u64) -> Option<Color> {
+    let mut renderer = FirstPixel::default();
+    for _ in 0..frames * M_CYCLES_PER_FRAME {
+        game_boy.tick(
+            &mut renderer,
+            &mut core::NoAudioTerminal,
+            &mut core::NoSerialConnection,
+        );
+    }
+    renderer.0
+}
+
+#[test]
+fn dmg_compatibility_mode() {
+    let mut game_boy = game_boy(0x04);
+    assert_eq!(game_boy.dump()[0xFF4D], 0x7E);
+    assert_eq!(run_frames(&mut game_boy, 2), Some(RED));
+    assert_eq!(game_boy.dump()[0xFF50], 0xFF);
+    assert_eq!(game_boy.dump()[0xFF4D], 0xFF);
+    assert_eq!(game_boy.dump()[0xFF4F], 0xFF);
+    assert_eq!(game_boy.dump()[0xFF68], 0xFF);
+    assert_eq!(game_boy.dump()[0xFF70], 0xFF);
+}
+
+#[test]
+fn cgb_mode_for_a_dmg_cartridge() {
+    let mut game_boy = game_boy(0x80);
+    assert_eq!(run_frames(&mut game_boy, 2), Some(RED));
+    assert_eq!(game_boy.dump()[0xFF4D], 0x7E);
+    assert_eq!(game_boy.dump()[0xFF4F], 0xFE);
+    assert_eq!(game_boy.dump()[0xFF68], 0xC2);
+    assert_eq!(game_boy.dump()[0xFF70], 0xF9);
+}
+
+#[test]
+fn dmg_compatibility_mode_is_restored_from_a_save_state() {
+    let mut compatible = game_boy(0x04);
+    run_frames(&mut compatible, 1);
+    let booting = game_boy(0x04);
+    let mut game_boy = game_boy(0x04);
+    game_boy.load_state(&compatible.save_state()).unwrap();
+    assert_eq!(game_boy.dump()[0xFF4D], 0xFF);
+    game_boy.load_state(&booting.save_state()).unwrap();
+    assert_eq!(game_boy.dump()[0xFF4D], 0x7E);
+}
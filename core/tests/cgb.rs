@@ -0,0 +1,212 @@
+mod harness;
+
+use core::GameBoy;
+
+const M_CYCLES: u64 = 40_000;
+
+const WAIT_FOR_VBLANK: [u8; 6] = [
+    0xF0, 0x44, // LDH A,(LY)
+    0xFE, 0x90, // CP $90
+    0x20, 0xFA, // JR NZ,-6
+];
+
+const SWITCH_SPEED: [u8; 6] = [
+    0x3E, 0x01, // LD A,$01
+    0xE0, 0x4D, // LDH (KEY1),A
+    0x10, 0x00, // STOP
+];
+
+const HDMA_SOURCE: [u8; 9] = [
+    0x3E, 0x02, // LD A,$02
+    0xE0, 0x51, // LDH (HDMA1),A
+    0xAF, // XOR A
+    0xE0, 0x52, // LDH (HDMA2),A
+    0xE0, 0x53, // LDH (HDMA3),A
+];
+
+fn game_boy(programs: &[&[u8]]) -> GameBoy {
+    harness::test_rom(&programs.concat())
+        .data(0x0200, &(0x00..0x20).collect::<Vec<_>>())
+        .cgb()
+        .game_boy()
+}
+
+fn run(programs: &[&[u8]]) -> GameBoy {
+    let mut game_boy = game_boy(programs);
+    harness::run(&mut game_boy, M_CYCLES);
+    game_boy
+}
+
+fn read(game_boy: &mut GameBoy, address: usize, length: usize) -> Vec<u8> {
+    game_boy.dump()[address..address + length].to_vec()
+}
+
+#[test]
+fn speed_switch() {
+    let mut game_boy = game_boy(&[]);
+    assert_eq!(game_boy.dump()[0xFF4D], 0x7E);
+    let mut game_boy = run(&[&[
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0x4D, // LDH (KEY1),A
+        0xF0, 0x4D, // LDH A,(KEY1)
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x10, 0x00, // STOP
+        0x18, 0xFE, // JR -2
+    ]]);
+    assert_eq!(game_boy.dump()[0xC000], 0x7F);
+    assert_eq!(game_boy.dump()[0xFF4D], 0xFE);
+}
+
+#[test]
+fn vram_banking() {
+    let mut game_boy = run(&[
+        &WAIT_FOR_VBLANK,
+        &[
+            0x3E, 0x11, // LD A,$11
+            0xEA, 0x00, 0x80, // LD ($8000),A
+            0x3E, 0x01, // LD A,$01
+            0xE0, 0x4F, // LDH (VBK),A
+            0x3E, 0x22, // LD A,$22
+            0xEA, 0x00, 0x80, // LD ($8000),A
+            0xF0, 0x4F, // LDH A,(VBK)
+            0xEA, 0x02, 0xC0, // LD ($C002),A
+            0xFA, 0x00, 0x80, // LD A,($8000)
+            0xEA, 0x01, 0xC0, // LD ($C001),A
+            0xAF, // XOR A
+            0xE0, 0x4F, // LDH (VBK),A
+            0xFA, 0x00, 0x80, // LD A,($8000)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x18, 0xFE, // JR -2
+        ],
+    ]);
+    assert_eq!(read(&mut game_boy, 0xC000, 3), [0x11, 0x22, 0xFF]);
+    assert_eq!(game_boy.dump()[0xFF4F], 0xFE);
+}
+
+#[test]
+fn wram_banking() {
+    let mut game_boy = run(&[&[
+        0x3E, 0x02, // LD A,$02
+        0xE0, 0x70, // LDH (SVBK),A
+        0x3E, 0x22, // LD A,$22
+        0xEA, 0x00, 0xD0, // LD ($D000),A
+        0xAF, // XOR A
+        0xE0, 0x70, // LDH (SVBK),A
+        0x3E, 0x11, // LD A,$11
+        0xEA, 0x00, 0xD0, // LD ($D000),A
+        0xF0, 0x70, // LDH A,(SVBK)
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x3E, 0x02, // LD A,$02
+        0xE0, 0x70, // LDH (SVBK),A
+        0xFA, 0x00, 0xD0, // LD A,($D000)
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x18, 0xFE, // JR -2
+    ]]);
+    assert_eq!(read(&mut game_boy, 0xC000, 2), [0x22, 0xF9]);
+    assert_eq!(game_boy.dump()[0xFF70], 0xFA);
+    assert_eq!(game_boy.dump()[0xD000], 0x22);
+}
+
+#[test]
+fn palette_auto_increment() {
+    let mut game_boy = run(&[&[
+        0x3E, 0x80, // LD A,$80
+        0xE0, 0x68, // LDH (BCPS),A
+        0x3E, 0x1F, // LD A,$1F
+        0xE0, 0x69, // LDH (BCPD),A
+        0x3E, 0x7C, // LD A,$7C
+        0xE0, 0x69, // LDH (BCPD),A
+        0xF0, 0x68, // LDH A,(BCPS)
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0x68, // LDH (BCPS),A
+        0xF0, 0x69, // LDH A,(BCPD)
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0xF0, 0x69, // LDH A,(BCPD)
+        0xEA, 0x02, 0xC0, // LD ($C002),A
+        0xF0, 0x68, // LDH A,(BCPS)
+        0xEA, 0x03, 0xC0, // LD ($C003),A
+        0x18, 0xFE, // JR -2
+    ]]);
+    assert_eq!(read(&mut game_boy, 0xC000, 4), [0xC2, 0x7C, 0x7C, 0x41]);
+}
+
+#[test]
+fn general_hdma() {
+    let mut game_boy = run(&[
+        &WAIT_FOR_VBLANK,
+        &HDMA_SOURCE,
+        &[
+            0xE0, 0x54, // LDH (HDMA4),A
+            0x3E, 0x01, // LD A,$01
+            0xE0, 0x55, // LDH (HDMA5),A
+            0xF0, 0x55, // LDH A,(HDMA5)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x21, 0x00, 0x80, // LD HL,$8000
+            0x11, 0x10, 0xC0, // LD DE,$C010
+            0x2A, // LD A,(HL+)
+            0x12, // LD (DE),A
+            0x13, // INC DE
+            0x7D, // LD A,L
+            0xFE, 0x20, // CP $20
+            0x20, 0xF8, // JR NZ,-8
+            0x18, 0xFE, // JR -2
+        ],
+    ]);
+    assert_eq!(game_boy.dump()[0xC000], 0xFF);
+    assert_eq!(
+        read(&mut game_boy, 0xC010, 0x20),
+        (0x00..0x20).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn hblank_hdma() {
+    let mut game_boy = run(&[
+        &WAIT_FOR_VBLANK,
+        &HDMA_SOURCE,
+        &[
+            0xE0, 0x54, // LDH (HDMA4),A
+            0x3E, 0x81, // LD A,$81
+            0xE0, 0x55, // LDH (HDMA5),A
+            0xF0, 0x55, // LDH A,(HDMA5)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0xAF, // XOR A
+            0xE0, 0x55, // LDH (HDMA5),A
+            0xF0, 0x55, // LDH A,(HDMA5)
+            0xEA, 0x01, 0xC0, // LD ($C001),A
+            0x18, 0xFE, // JR -2
+        ],
+    ]);
+    assert_eq!(read(&mut game_boy, 0xC000, 2), [0x01, 0x81]);
+}
+
+fn hdma_timer(speed_switch: &[u8], hdma5: u8) -> u8 {
+    let mut game_boy = run(&[
+        speed_switch,
+        &WAIT_FOR_VBLANK,
+        &HDMA_SOURCE,
+        &[
+            0xE0, 0x54, // LDH (HDMA4),A
+            0x3E, 0x05, // LD A,$05
+            0xE0, 0x07, // LDH (TAC),A
+            0xAF, // XOR A
+            0xE0, 0x05, // LDH (TIMA),A
+            0x3E, 0x01, // LD A,$01
+            0xE0, hdma5, // LDH (HDMA5),A
+            0xF0, 0x05, // LDH A,(TIMA)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x18, 0xFE, // JR -2
+        ],
+    ]);
+    game_boy.dump()[0xC000]
+}
+
+#[test]
+fn general_hdma_stalls_cpu() {
+    assert_eq!(hdma_timer(&[], 0x55) - hdma_timer(&[], 0x80), 4);
+    assert_eq!(
+        hdma_timer(&SWITCH_SPEED, 0x55) - hdma_timer(&SWITCH_SPEED, 0x80),
+        8
+    );
+}
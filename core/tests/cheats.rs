@@ -0,0 +1,77 @@
+mod harness;
+
+use core::{Cheat, CheatCode, GameBoy, GameGenieCode};
+use harness::TestRom;
+
+const M_CYCLES: u64 = core::M_CYCLES_PER_FRAME * 3;
+
+fn test_rom(address: u16) -> TestRom {
+    let [low, high] = address.to_le_bytes();
+    harness::test_rom(&[
+        0xFA, low, high, // LD A,(address)
+        0x47, // LD B,A
+        0x18, 0xFA, // JR -6
+    ])
+    .data(0x0200, &[0x12])
+}
+
+fn game_boy(test_rom: TestRom, codes: &[&str]) -> GameBoy {
+    let mut game_boy = test_rom.game_boy();
+    for code in codes {
+        game_boy.add_cheat(code.parse().unwrap());
+    }
+    game_boy
+}
+
+fn run(game_boy: &mut GameBoy) -> u8 {
+    harness::run(game_boy, M_CYCLES);
+    game_boy.registers().b
+}
+
+#[test]
+fn parses_codes() {
+    let cheat: Cheat = "342-00f-aa2".parse().unwrap();
+    assert_eq!(cheat.to_string(), "342-00F-AA2");
+    assert_eq!(
+        *cheat.code(),
+        CheatCode::GameGenie(GameGenieCode {
+            address: 0x0200,
+            value: 0x34,
+            compare: Some(0x12),
+        })
+    );
+    assert!("342-00F-AA".parse::<Cheat>().is_err());
+    assert!("342-007".parse::<Cheat>().is_err());
+    assert!("025600C0".parse::<Cheat>().is_err());
+}
+
+#[test]
+fn game_genie() {
+    assert_eq!(run(&mut game_boy(test_rom(0x0200), &[])), 0x12);
+    assert_eq!(run(&mut game_boy(test_rom(0x0200), &["342-00F"])), 0x34);
+    assert_eq!(run(&mut game_boy(test_rom(0x0200), &["342-00F-AA2"])), 0x34);
+    assert_eq!(run(&mut game_boy(test_rom(0x0200), &["342-00F-BA2"])), 0x12);
+}
+
+#[test]
+fn game_shark() {
+    let mut game_boy = game_boy(test_rom(0xC000), &["015600C0"]);
+    assert_eq!(run(&mut game_boy), 0x56);
+    game_boy.set_cheat_enabled(0, false);
+    assert!(!game_boy.cheats()[0].is_enabled);
+    assert!(game_boy.remove_cheat(0).is_some());
+    assert!(game_boy.remove_cheat(0).is_none());
+}
+
+#[test]
+fn game_shark_external_ram_bank() {
+    let mut game_boy = game_boy(
+        test_rom(0xC000).cartridge_type(0x03).ram_size(0x03),
+        &["827704A0", "8188FFBF"],
+    );
+    run(&mut game_boy);
+    let ram = game_boy.cartridge().save_data().unwrap();
+    assert_eq!(ram[0x4004], 0x77);
+    assert_eq!(ram[0x3FFF], 0x88);
+    assert_eq!(ram[0x0004], 0xFF);
+}
@@ -0,0 +1,151 @@
+mod harness;
+
+use core::{
+    Breakpoint, Debugger, DebuggerCommand, DebuggerEvent, GameBoy, NoAudioTerminal, NoRenderer,
+    NoSerialConnection,
+};
+
+const M_CYCLES: u64 = 10_000;
+
+fn call_game_boy() -> GameBoy {
+    harness::test_rom(&[
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x00, // NOP
+        0x18, 0xFE, // JR -2
+    ])
+    .data(
+        0x0200,
+        &[
+            0x00, // NOP
+            0x00, // NOP
+            0xC9, // RET
+        ],
+    )
+    .game_boy()
+}
+
+fn run(
+    debugger: &mut Debugger,
+    game_boy: &mut GameBoy,
+    command: DebuggerCommand,
+) -> Option<DebuggerEvent> {
+    debugger.resume(game_boy, command);
+    (0..M_CYCLES).find_map(|_| {
+        debugger.tick(
+            game_boy,
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut NoSerialConnection,
+        )
+    })
+}
+
+fn run_to(debugger: &mut Debugger, game_boy: &mut GameBoy, address: u16) {
+    let event = run(
+        debugger,
+        game_boy,
+        DebuggerCommand::RunTo(Breakpoint::new(None, address)),
+    );
+    assert_eq!(event, Some(DebuggerEvent::Stepped));
+    assert_eq!(game_boy.registers().pc, address);
+}
+
+#[test]
+fn step_over() {
+    let mut game_boy = call_game_boy();
+    let mut debugger = Debugger::default();
+    run_to(&mut debugger, &mut game_boy, 0x0150);
+    let sp = game_boy.registers().sp;
+    assert_eq!(
+        run(&mut debugger, &mut game_boy, DebuggerCommand::StepOver),
+        Some(DebuggerEvent::Stepped)
+    );
+    assert_eq!(game_boy.registers().pc, 0x0153);
+    assert_eq!(game_boy.registers().sp, sp);
+}
+
+#[test]
+fn step_into() {
+    let mut game_boy = call_game_boy();
+    let mut debugger = Debugger::default();
+    run_to(&mut debugger, &mut game_boy, 0x0150);
+    assert_eq!(
+        run(&mut debugger, &mut game_boy, DebuggerCommand::StepInto),
+        Some(DebuggerEvent::Stepped)
+    );
+    assert_eq!(game_boy.registers().pc, 0x0200);
+}
+
+#[test]
+fn step_out() {
+    let mut game_boy = call_game_boy();
+    let mut debugger = Debugger::default();
+    run_to(&mut debugger, &mut game_boy, 0x0201);
+    assert_eq!(
+        run(&mut debugger, &mut game_boy, DebuggerCommand::StepOut),
+        Some(DebuggerEvent::Stepped)
+    );
+    assert_eq!(game_boy.registers().pc, 0x0153);
+}
+
+#[test]
+fn banked_breakpoint() {
+    let mut game_boy = harness::test_rom(&[
+        0x3E, 0x02, // LD A,$02
+        0xEA, 0x00, 0x20, // LD ($2000),A
+        0xCD, 0x00, 0x40, // CALL $4000
+        0x18, 0xFB, // JR -5
+    ])
+    .cartridge_type(0x01)
+    .rom_size(0x01)
+    .data(2 * 0x4000, &[0xC9])
+    .game_boy();
+    let mut debugger = Debugger::default();
+    debugger.add_breakpoint(Breakpoint::new(Some(1), 0x4000));
+    assert_eq!(
+        run(&mut debugger, &mut game_boy, DebuggerCommand::Continue),
+        None
+    );
+    let breakpoint = Breakpoint::new(Some(2), 0x4000);
+    debugger.add_breakpoint(breakpoint);
+    assert_eq!(
+        run(&mut debugger, &mut game_boy, DebuggerCommand::Continue),
+        Some(DebuggerEvent::Breakpoint(breakpoint))
+    );
+    assert_eq!(game_boy.registers().pc, 0x4000);
+}
+
+#[test]
+fn double_speed() {
+    let mut game_boy = harness::test_rom(&[
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0x4D, // LDH (KEY1),A
+        0x10, 0x00, // STOP
+        0x00, // NOP
+        0x00, // NOP
+        0x00, // NOP
+        0x00, // NOP
+        0x18, 0xFA, // JR -6
+    ])
+    .cgb()
+    .game_boy();
+    let mut debugger = Debugger::default();
+    run_to(&mut debugger, &mut game_boy, 0x0156);
+    assert_eq!(game_boy.read(0xFF4D), 0xFE);
+    for address in 0x0157..=0x015A {
+        assert_eq!(
+            run(&mut debugger, &mut game_boy, DebuggerCommand::StepInto),
+            Some(DebuggerEvent::Stepped)
+        );
+        assert_eq!(game_boy.registers().pc, address);
+    }
+    for address in [0x0157, 0x0158] {
+        let breakpoint = Breakpoint::new(None, address);
+        debugger.add_breakpoint(breakpoint);
+        assert_eq!(
+            run(&mut debugger, &mut game_boy, DebuggerCommand::Continue),
+            Some(DebuggerEvent::Breakpoint(breakpoint))
+        );
+        assert_eq!(game_boy.registers().pc, address);
+    }
+}
@@ -0,0 +1,104 @@
+mod harness;
+
+use core::{disassemble, Disassembler, DisassemblerError, Disassembly};
+
+fn rom() -> Vec<u8> {
+    harness::test_rom(&[
+        0xC3, 0x50, 0x01, // JP $0150
+        0x18, 0xFE, // JR -2
+        0xCD, 0x00, 0x40, // CALL $4000
+        0x20, 0x03, // JR NZ,+3
+        0xE0, 0x40, // LDH ($40),A
+    ])
+    .build()
+}
+
+fn text_and_comment(disassembly: Disassembly) -> (String, Option<String>) {
+    (disassembly.text, disassembly.comment)
+}
+
+#[test]
+fn operands() {
+    let rom = rom();
+    let disassembler = Disassembler::new(&rom);
+    let text = |address| disassembler.disassemble(0, address).unwrap().text;
+    assert_eq!(text(0x0150), "JP $0150");
+    assert_eq!(text(0x0153), "JR $FE");
+    assert_eq!(text(0x0155), "CALL $4000");
+    assert_eq!(text(0x0158), "JR NZ, $03");
+    assert_eq!(text(0x015A), "LD ($FF40), A");
+    assert_eq!(
+        disassembler.disassemble(0, 0x0155).unwrap(),
+        Disassembly {
+            bank: Some(0),
+            address: 0x0155,
+            bytes: vec![0xCD, 0x00, 0x40],
+            text: "CALL $4000".into(),
+            comment: None,
+        }
+    );
+    assert_eq!(
+        disassemble(&[0xC3, 0x50, 0x01], 0x0100),
+        Disassembly {
+            bank: None,
+            address: 0x0100,
+            bytes: vec![0xC3, 0x50, 0x01],
+            text: "JP $0150".into(),
+            comment: None,
+        }
+    );
+}
+
+#[test]
+fn annotations() {
+    let rom = rom();
+    let disassembler = Disassembler::new(&rom).annotates(true);
+    let disassemble = |address| text_and_comment(disassembler.disassemble(0, address).unwrap());
+    assert_eq!(disassemble(0x0150), ("JP $0150".into(), None));
+    assert_eq!(
+        disassemble(0x0153),
+        ("JR $FE".into(), Some("to $0153".into()))
+    );
+    assert_eq!(disassemble(0x0155), ("CALL $4000".into(), None));
+    assert_eq!(
+        disassemble(0x0158),
+        ("JR NZ, $03".into(), Some("to $015D".into()))
+    );
+    assert_eq!(
+        disassemble(0x015A),
+        ("LD ($FF40), A".into(), Some("LCDC".into()))
+    );
+    assert_eq!(
+        disassemble(0x0100),
+        ("NOP".into(), Some("Entry point".into()))
+    );
+    assert_eq!(
+        disassemble(0x0147),
+        ("DB $00".into(), Some("Cartridge type".into()))
+    );
+}
+
+#[test]
+fn banks() {
+    let rom = rom();
+    let disassembler = Disassembler::new(&rom);
+    assert_eq!(disassembler.bank_count(), 2);
+    assert_eq!(disassembler.disassemble(1, 0x4000).unwrap().bank, Some(1));
+    assert_eq!(
+        disassembler.disassemble(2, 0x4000),
+        Err(DisassemblerError::BankOutOfRange(2))
+    );
+    assert_eq!(
+        disassembler.disassemble(1, 0x0150),
+        Err(DisassemblerError::AddressOutOfBank(0x0150))
+    );
+    let instructions = disassembler.disassemble_bank(0, 0x0150).unwrap();
+    assert_eq!(
+        instructions[..3]
+            .iter()
+            .map(|disassembly| disassembly.address)
+            .collect::<Vec<_>>(),
+        [0x0150, 0x0153, 0x0155]
+    );
+    assert_eq!(instructions.last().unwrap().address, 0x3FFF);
+}
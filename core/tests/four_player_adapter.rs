@@ -0,0 +1,85 @@
+mod harness;
+
+use core::{FourPlayerAdapter, GameBoy, NoAudioTerminal, NoRenderer, NoSerialConnection};
+
+const M_CYCLES: u64 = 100_000;
+
+fn game_boy(script: &[u8]) -> GameBoy {
+    let length = (script.len() as u16).to_le_bytes();
+    harness::test_rom(&[
+        0x21, 0x00, 0x02, // LD HL,$0200
+        0x11, length[0], length[1], // LD DE,length
+        0x01, 0x00, 0xC0, // LD BC,$C000
+        0x2A, // LD A,(HL+)
+        0xE0, 0x01, // LDH (SB),A
+        0x3E, 0x80, // LD A,$80
+        0xE0, 0x02, // LDH (SC),A
+        0xF0, 0x02, // LDH A,(SC)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,-6
+        0xF0, 0x01, // LDH A,(SB)
+        0x02, // LD (BC),A
+        0x03, // INC BC
+        0x1B, // DEC DE
+        0x7A, // LD A,D
+        0xB3, // OR E
+        0x20, 0xEA, // JR NZ,-22
+        0x18, 0xFE, // JR -2
+    ])
+    .data(0x0200, script)
+    .game_boy()
+}
+
+fn received(game_boy: &mut GameBoy, length: usize) -> Vec<u8> {
+    (0..length as u16)
+        .map(|offset| game_boy.read(0xC000 + offset))
+        .collect()
+}
+
+#[test]
+fn exchanges_packets() {
+    let ping = [0x88, 0x88, 0x00, 0x01];
+    let player1: Vec<u8> = [ping, ping, [0xAA; 4], [0x00; 4]]
+        .iter()
+        .chain(&[[0x11, 0, 0, 0], [0x12, 0, 0, 0], [0x13, 0, 0, 0]])
+        .flatten()
+        .copied()
+        .collect();
+    let player2: Vec<u8> = [ping, ping, ping, [0x00; 4]]
+        .iter()
+        .chain(&[[0x21, 0, 0, 0], [0x22, 0, 0, 0], [0x23, 0, 0, 0]])
+        .flatten()
+        .copied()
+        .collect();
+    let mut game_boys = [game_boy(&player1), game_boy(&player2)];
+    let mut adapter = FourPlayerAdapter::default();
+    for _ in 0..M_CYCLES {
+        for game_boy in game_boys.iter_mut() {
+            game_boy.tick(
+                &mut NoRenderer,
+                &mut NoAudioTerminal,
+                &mut NoSerialConnection,
+            );
+        }
+        adapter.tick(&mut game_boys);
+    }
+    assert!(adapter.is_transmitting());
+    assert!(adapter.is_connected(0));
+    assert!(adapter.is_connected(1));
+    assert!(!adapter.is_connected(2));
+    assert_eq!(adapter.packet_size(), 1);
+    assert_eq!(
+        received(&mut game_boys[0], player1.len()),
+        [
+            0xFE, 0x01, 0x01, 0x01, 0xFE, 0x31, 0x31, 0x31, 0xFE, 0x31, 0x31, 0x31, 0xCC, 0xCC,
+            0xCC, 0xCC, 0x00, 0x00, 0x00, 0x00, 0x11, 0x21, 0x00, 0x00, 0x12, 0x22, 0x00, 0x00,
+        ]
+    );
+    assert_eq!(
+        received(&mut game_boys[1], player2.len()),
+        [
+            0xFE, 0x02, 0x02, 0x02, 0xFE, 0x32, 0x32, 0x32, 0xFE, 0x32, 0x32, 0x32, 0xCC, 0xCC,
+            0xCC, 0xCC, 0x00, 0x00, 0x00, 0x00, 0x11, 0x21, 0x00, 0x00, 0x12, 0x22, 0x00, 0x00,
+        ]
+    );
+}
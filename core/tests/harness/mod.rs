@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+
+use core::{
+    display_size, BufferedSerialConnection, Cartridge, Color, GameBoy, NoAudioTerminal, NoRenderer,
+    NoSerialConnection, Renderer, Vec2, M_CYCLES, M_CYCLES_PER_FRAME,
+};
+use std::{env, fs, path::PathBuf, rc::Rc};
+
+const ENTRY_POINT: [u8; 4] = [0x00, 0xC3, 0x50, 0x01];
+
+const PROGRAM_ADDRESS: usize = 0x0150;
+
+const ROM_BANK_SIZE: usize = 0x4000;
+
+const LD_B_B: u8 = 0x40;
+
+const MOONEYE_PASS_SIGNATURE: [u8; 6] = [3, 5, 8, 13, 21, 34];
+
+const TIMEOUT_M_CYCLES: u64 = 60 * M_CYCLES;
+
+#[derive(Debug, Clone, Default)]
+pub struct TestRom {
+    program: Vec<u8>,
+    cartridge_type: u8,
+    rom_size: u8,
+    ram_size: u8,
+    is_cgb: bool,
+    has_bank_markers: bool,
+    data: Vec<(usize, Vec<u8>)>,
+}
+
+pub fn test_rom(program: &[u8]) -> TestRom {
+    TestRom {
+        program: program.into(),
+        ..Default::default()
+    }
+}
+
+pub fn game_boy(program: &[u8]) -> GameBoy {
+    test_rom(program).game_boy()
+}
+
+impl TestRom {
+    pub fn cartridge_type(mut self, cartridge_type: u8) -> Self {
+        self.cartridge_type = cartridge_type;
+        self
+    }
+
+    pub fn rom_size(mut self, rom_size: u8) -> Self {
+        self.rom_size = rom_size;
+        self
+    }
+
+    pub fn ram_size(mut self, ram_size: u8) -> Self {
+        self.ram_size = ram_size;
+        self
+    }
+
+    pub fn cgb(mut self) -> Self {
+        self.is_cgb = true;
+        self
+    }
+
+    pub fn bank_markers(mut self) -> Self {
+        self.has_bank_markers = true;
+        self
+    }
+
+    pub fn data(mut self, address: usize, data: &[u8]) -> Self {
+        self.data.push((address, data.into()));
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        let mut rom = vec![0; 0x8000 << self.rom_size];
+        if self.has_bank_markers {
+            for bank in 0..rom.len() / ROM_BANK_SIZE {
+                rom[bank * ROM_BANK_SIZE] = bank as u8;
+            }
+        }
+        rom[0x0100..0x0104].copy_from_slice(&ENTRY_POINT);
+        if self.is_cgb {
+            rom[0x0143] = 0x80;
+        }
+        rom[0x0147] = self.cartridge_type;
+        rom[0x0148] = self.rom_size;
+        rom[0x0149] = self.ram_size;
+        rom[PROGRAM_ADDRESS..PROGRAM_ADDRESS + self.program.len()].copy_from_slice(&self.program);
+        for (address, data) in self.data {
+            rom[address..address + data.len()].copy_from_slice(&data);
+        }
+        rom
+    }
+
+    pub fn cartridge(self) -> Cartridge {
+        Cartridge::new(Rc::new(self.build())).expect("Could not load the cartridge")
+    }
+
+    pub fn game_boy(self) -> GameBoy {
+        GameBoy::new(self.cartridge())
+    }
+}
+
+pub fn run(game_boy: &mut GameBoy, m_cycles: u64) {
+    for _ in 0..m_cycles {
+        game_boy.tick(
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut NoSerialConnection,
+        );
+    }
+}
+
+pub fn rom_dir() -> PathBuf {
+    env::var_os("UUGB_TEST_ROMS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../test-roms"))
+}
+
+fn load(path: &str) -> GameBoy {
+    let path = rom_dir().join(path);
+    let rom = fs::read(&path)
+        .unwrap_or_else(|error| panic!("Could not read {}: {}", path.display(), error));
+    GameBoy::new(Cartridge::new(Rc::new(rom)).expect("Could not load the cartridge"))
+}
+
+#[derive(Debug)]
+pub struct Screen {
+    pixels: Vec<Color>,
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        let size = display_size();
+        Self {
+            pixels: vec![Color::White; size.x as usize * size.y as usize],
+        }
+    }
+}
+
+impl Renderer for Screen {
+    fn render(&mut self, position: Vec2, color: Color) {
+        self.pixels[position.y as usize * display_size().x as usize + position.x as usize] = color;
+    }
+}
+
+fn shade(color: &Color) -> u8 {
+    match color {
+        Color::White => 0xFF,
+        Color::LightGray => 0xAA,
+        Color::DarkGray => 0x55,
+        Color::Black => 0x00,
+        Color::Rgb(rgb) => ((rgb.red as u16 + rgb.green as u16 + rgb.blue as u16) * 255 / 93) as u8,
+    }
+}
+
+fn runs_breakpoint(game_boy: &mut GameBoy) -> bool {
+    game_boy.is_ready_for_instruction() && game_boy.read(game_boy.registers().pc) == LD_B_B
+}
+
+fn run_until_breakpoint(game_boy: &mut GameBoy, renderer: &mut impl Renderer) -> bool {
+    for _ in 0..TIMEOUT_M_CYCLES {
+        game_boy.tick(renderer, &mut NoAudioTerminal, &mut NoSerialConnection);
+        if runs_breakpoint(game_boy) {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn run_blargg(path: &str) {
+    let mut game_boy = load(path);
+    let mut serial_connection = BufferedSerialConnection::default();
+    let mut text = String::new();
+    for _ in 0..TIMEOUT_M_CYCLES / M_CYCLES_PER_FRAME {
+        for _ in 0..M_CYCLES_PER_FRAME {
+            game_boy.tick(
+                &mut NoRenderer,
+                &mut NoAudioTerminal,
+                &mut serial_connection,
+            );
+        }
+        text.push_str(&serial_connection.take_text());
+        if text.contains("Passed") {
+            return;
+        }
+        if text.contains("Failed") {
+            panic!("{} failed:\n{}", path, text);
+        }
+    }
+    panic!("{} timed out:\n{}", path, text);
+}
+
+pub fn run_mooneye(path: &str) {
+    let mut game_boy = load(path);
+    assert!(
+        run_until_breakpoint(&mut game_boy, &mut NoRenderer),
+        "{} timed out",
+        path
+    );
+    let registers = game_boy.registers();
+    let signature = [
+        registers.b,
+        registers.c,
+        registers.d,
+        registers.e,
+        registers.h,
+        registers.l,
+    ];
+    assert_eq!(
+        signature, MOONEYE_PASS_SIGNATURE,
+        "{} failed: {}",
+        path, registers
+    );
+}
+
+pub fn run_screenshot(path: &str, reference_path: &str) {
+    let mut game_boy = load(path);
+    let reference = fs::File::open(rom_dir().join(reference_path))
+        .unwrap_or_else(|_| panic!("Could not open {}", reference_path));
+    let mut reader = png::Decoder::new(reference)
+        .read_info()
+        .expect("Could not decode the reference image");
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .expect("Could not decode the reference image");
+    let mut screen = Screen::default();
+    assert!(
+        run_until_breakpoint(&mut game_boy, &mut screen),
+        "{} timed out",
+        path
+    );
+    for _ in 0..M_CYCLES_PER_FRAME {
+        game_boy.tick(&mut screen, &mut NoAudioTerminal, &mut NoSerialConnection);
+    }
+    let channels = info.color_type.samples();
+    let mismatches = screen
+        .pixels
+        .iter()
+        .enumerate()
+        .filter(|(index, color)| {
+            let expected = buffer[index * channels];
+            shade(color).abs_diff(expected) > 0x20
+        })
+        .count();
+    assert_eq!(mismatches, 0, "{} differs from {}", path, reference_path);
+}
@@ -0,0 +1,170 @@
+mod harness;
+
+use core::{
+    AudioFrame, AudioTerminal, Cartridge, GameBoy, NoRenderer, NoSerialConnection, RtcClock,
+};
+
+const RAM_SIZE: usize = 0x8000;
+
+fn cartridge(cartridge_type: u8) -> Cartridge {
+    harness::test_rom(&[])
+        .cartridge_type(cartridge_type)
+        .rom_size(0x02)
+        .ram_size(0x03)
+        .bank_markers()
+        .cartridge()
+}
+
+#[derive(Default)]
+struct AudioRecorder {
+    frames: Vec<AudioFrame>,
+}
+
+impl AudioTerminal for AudioRecorder {
+    fn output(&mut self, frame: AudioFrame) {
+        self.frames.push(frame);
+    }
+}
+
+fn record_audio(game_boy: &mut GameBoy) -> Vec<AudioFrame> {
+    let mut recorder = AudioRecorder::default();
+    for _ in 0..core::M_CYCLES / 100 {
+        game_boy.tick(&mut NoRenderer, &mut recorder, &mut NoSerialConnection);
+    }
+    recorder.frames
+}
+
+fn execute(cartridge: &mut Cartridge, commands: &[u8]) {
+    cartridge.write_rom(0x0000, 0x0B);
+    for command in commands {
+        cartridge.write_ram(0x0000, *command);
+    }
+}
+
+fn response(cartridge: &mut Cartridge) -> u8 {
+    cartridge.write_rom(0x0000, 0x0C);
+    cartridge.read_ram(0x0000) & 0xF
+}
+
+fn set_time(cartridge: &mut Cartridge, minutes: u16, days: u16) {
+    execute(cartridge, &[0x40, 0x50]);
+    for value in [minutes, days] {
+        for nibble in 0..3 {
+            execute(cartridge, &[0x30 | (value >> (nibble * 4) & 0xF) as u8]);
+        }
+    }
+    execute(cartridge, &[0x61]);
+}
+
+fn read_time(cartridge: &mut Cartridge) -> (u16, u16) {
+    execute(cartridge, &[0x60, 0x40, 0x50]);
+    let mut read_value = || {
+        (0..3).fold(0, |value, nibble| {
+            execute(cartridge, &[0x10]);
+            value | (response(cartridge) as u16) << (nibble * 4)
+        })
+    };
+    (read_value(), read_value())
+}
+
+#[test]
+fn huc1_banking() {
+    let mut cartridge = cartridge(0xFF);
+    cartridge.write_rom(0x2000, 0x05);
+    assert_eq!(cartridge.read_rom(0x4000), 0x05);
+    cartridge.write_rom(0x4000, 0x02);
+    cartridge.write_ram(0x0010, 0x42);
+    cartridge.write_rom(0x4000, 0x01);
+    assert_ne!(cartridge.read_ram(0x0010), 0x42);
+    cartridge.write_rom(0x4000, 0x02);
+    assert_eq!(cartridge.read_ram(0x0010), 0x42);
+}
+
+#[test]
+fn huc1_ir_mode() {
+    let mut cartridge = cartridge(0xFF);
+    cartridge.write_ram(0x0000, 0x42);
+    cartridge.write_rom(0x0000, 0x0E);
+    assert_eq!(cartridge.read_ram(0x0000), 0xC0);
+    cartridge.write_ram(0x0000, 0x01);
+    cartridge.write_rom(0x0000, 0x00);
+    assert_eq!(cartridge.read_ram(0x0000), 0x42);
+}
+
+#[test]
+fn huc3_banking() {
+    let mut cartridge = cartridge(0xFE);
+    assert!(cartridge.has_battery());
+    cartridge.write_rom(0x2000, 0x07);
+    assert_eq!(cartridge.read_rom(0x4000), 0x07);
+    cartridge.write_rom(0x0000, 0x0A);
+    cartridge.write_rom(0x4000, 0x03);
+    cartridge.write_ram(0x0000, 0x42);
+    cartridge.write_rom(0x0000, 0x00);
+    cartridge.write_ram(0x0000, 0x24);
+    assert_eq!(cartridge.read_ram(0x0000), 0x42);
+    cartridge.write_rom(0x0000, 0x0D);
+    assert_eq!(cartridge.read_ram(0x0000) & 0x01, 0x01);
+}
+
+#[test]
+fn huc3_rtc_commands() {
+    let mut cartridge = cartridge(0xFE);
+    execute(&mut cartridge, &[0x62]);
+    assert_eq!(response(&mut cartridge), 0x1);
+    set_time(&mut cartridge, 24 * 60 - 1, 0x123);
+    for _ in 0..60 * core::M_CYCLES {
+        cartridge.tick();
+    }
+    assert_eq!(read_time(&mut cartridge), (0, 0x124));
+}
+
+#[test]
+fn huc3_persists_the_clock() {
+    let mut cartridge = cartridge(0xFE);
+    cartridge.set_rtc_clock(RtcClock::Fixed(1_000_000));
+    set_time(&mut cartridge, 100, 7);
+    let data = cartridge.save_data().unwrap();
+    assert_eq!(data.len(), RAM_SIZE + 12);
+    assert_eq!(data[RAM_SIZE..RAM_SIZE + 8], 1_000_000i64.to_le_bytes());
+    let mut restored = self::cartridge(0xFE);
+    restored.set_rtc_clock(RtcClock::Fixed(1_000_000 + 2 * 3600 + 30));
+    restored.load_save_data(&data);
+    assert_eq!(read_time(&mut restored), (220, 7));
+    let mut emulated = self::cartridge(0xFE);
+    emulated.set_rtc_clock(RtcClock::Emulated);
+    emulated.load_save_data(&data);
+    assert_eq!(read_time(&mut emulated), (100, 7));
+}
+
+#[test]
+fn huc3_tone() {
+    let mut cartridge = cartridge(0xFE);
+    assert_eq!(cartridge.tone(), None);
+    execute(&mut cartridge, &[0x47, 0x52, 0x33]);
+    assert_eq!(cartridge.tone(), Some(0x3));
+    execute(&mut cartridge, &[0x47, 0x52, 0x30]);
+    assert_eq!(cartridge.tone(), None);
+}
+
+#[test]
+fn huc3_tone_is_mixed_into_the_audio() {
+    let game_boy = || {
+        harness::test_rom(&[0x18, 0xFE])
+            .cartridge_type(0xFE)
+            .rom_size(0x02)
+            .ram_size(0x03)
+            .game_boy()
+    };
+    let mut silent = game_boy();
+    let mut beeping = game_boy();
+    execute(beeping.cartridge_mut(), &[0x47, 0x52, 0x33]);
+    let silent_frames = record_audio(&mut silent);
+    let beeping_frames = record_audio(&mut beeping);
+    let raised = silent_frames
+        .iter()
+        .zip(&beeping_frames)
+        .filter(|(silent, beeping)| beeping.left > silent.left && beeping.right > silent.right)
+        .count();
+    assert!(raised * 3 > beeping_frames.len() && raised * 3 < beeping_frames.len() * 2);
+}
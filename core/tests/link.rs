@@ -0,0 +1,119 @@
+mod harness;
+
+use core::{GameBoy, LinkPort, NoAudioTerminal, NoRenderer, SerialConnection};
+
+const M_CYCLES: u64 = 100_000;
+
+fn game_boy(data: u8, control: u8) -> GameBoy {
+    harness::game_boy(&[
+        0x3E, data, // LD A,data
+        0xE0, 0x01, // LDH (SB),A
+        0x3E, control, // LD A,control
+        0xE0, 0x02, // LDH (SC),A
+        0xF0, 0x02, // LDH A,(SC)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,-6
+        0xF0, 0x01, // LDH A,(SB)
+        0x47, // LD B,A
+        0x18, 0xFE, // JR -2
+    ])
+}
+
+fn run(game_boy: &mut GameBoy, serial_connection: &mut impl SerialConnection, m_cycles: u64) -> u8 {
+    for _ in 0..m_cycles {
+        game_boy.tick(&mut NoRenderer, &mut NoAudioTerminal, serial_connection);
+    }
+    game_boy.registers().b
+}
+
+#[test]
+fn link_port() {
+    let mut master = game_boy(0x42, 0x81);
+    let mut slave = game_boy(0x99, 0x80);
+    for _ in 0..M_CYCLES {
+        master.tick(
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut LinkPort::new(&mut slave),
+        );
+        slave.tick(
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut LinkPort::new(&mut master),
+        );
+    }
+    assert_eq!(master.registers().b, 0x99);
+    assert_eq!(slave.registers().b, 0x42);
+}
+
+#[test]
+fn link_port_without_peer_clock() {
+    let mut first = game_boy(0x42, 0x81);
+    let mut second = game_boy(0x99, 0x81);
+    for _ in 0..M_CYCLES {
+        first.tick(
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut LinkPort::new(&mut second),
+        );
+        second.tick(
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut LinkPort::new(&mut first),
+        );
+    }
+    assert_eq!(first.registers().b, 0xFF);
+    assert_eq!(second.registers().b, 0xFF);
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_connection() {
+    use core::SocketConnection;
+    use std::{net::Shutdown, os::unix::net::UnixStream, sync::mpsc, thread};
+    const SETUP_M_CYCLES: u64 = 100;
+    let (master_stream, slave_stream) = UnixStream::pair().unwrap();
+    let (ready_sender, ready_receiver) = mpsc::channel();
+    let slave = thread::spawn(move || {
+        let mut link = SocketConnection::from_unix(slave_stream).unwrap();
+        let mut game_boy = game_boy(0x99, 0x80);
+        run(&mut game_boy, &mut link, SETUP_M_CYCLES);
+        ready_sender.send(()).unwrap();
+        while link.is_connected() {
+            run(&mut game_boy, &mut link, 1);
+        }
+        game_boy.registers().b
+    });
+    let mut link = SocketConnection::from_unix(master_stream.try_clone().unwrap()).unwrap();
+    ready_receiver.recv().unwrap();
+    assert_eq!(run(&mut game_boy(0x42, 0x81), &mut link, M_CYCLES), 0x99);
+    assert!(link.is_connected());
+    master_stream.shutdown(Shutdown::Both).unwrap();
+    assert_eq!(slave.join().unwrap(), 0x42);
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_connection_without_reply() {
+    use core::SocketConnection;
+    use std::{io::Read, os::unix::net::UnixStream};
+    let (master_stream, mut peer_stream) = UnixStream::pair().unwrap();
+    let mut link = SocketConnection::from_unix(master_stream).unwrap();
+    assert_eq!(run(&mut game_boy(0x42, 0x81), &mut link, M_CYCLES), 0xFF);
+    assert!(link.is_connected());
+    peer_stream.set_nonblocking(true).unwrap();
+    let mut messages = [0; 8];
+    assert_eq!(peer_stream.read(&mut messages).unwrap(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_connection_with_closed_peer() {
+    use core::SocketConnection;
+    use std::os::unix::net::UnixStream;
+    let (master_stream, peer_stream) = UnixStream::pair().unwrap();
+    drop(peer_stream);
+    let mut link = SocketConnection::from_unix(master_stream).unwrap();
+    assert_eq!(run(&mut game_boy(0x42, 0x81), &mut link, M_CYCLES), 0xFF);
+    assert!(!link.is_connected());
+}
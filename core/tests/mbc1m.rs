@@ -0,0 +1,54 @@
+mod harness;
+
+use core::NINTENDO_LOGO;
+
+const GAME_SIZE: usize = 0x4_0000;
+
+const GAME_COUNT: usize = 4;
+
+const PROGRAM: &[u8] = &[
+    0x3E, 0x01, // LD A,$01
+    0xEA, 0x00, 0x40, // LD ($4000),A
+    0x3E, 0x02, // LD A,$02
+    0xEA, 0x00, 0x20, // LD ($2000),A
+    0xFA, 0x00, 0x40, // LD A,($4000)
+    0x47, // LD B,A
+    0x3E, 0x01, // LD A,$01
+    0xEA, 0x00, 0x60, // LD ($6000),A
+    0xFA, 0x00, 0x00, // LD A,($0000)
+    0x4F, // LD C,A
+    0x3E, 0x10, // LD A,$10
+    0xEA, 0x00, 0x20, // LD ($2000),A
+    0xFA, 0x00, 0x40, // LD A,($4000)
+    0x57, // LD D,A
+    0x18, 0xFE, // JR -2
+];
+
+fn run(game_logos: usize) -> (u8, u8, u8) {
+    let mut test_rom = harness::test_rom(PROGRAM)
+        .cartridge_type(0x01)
+        .rom_size(0x05)
+        .bank_markers();
+    for game in 0..GAME_COUNT {
+        let start = game * GAME_SIZE;
+        if game <= game_logos {
+            test_rom = test_rom.data(start + 0x0104, &NINTENDO_LOGO);
+        }
+        test_rom = test_rom.data(start + 0x0150, PROGRAM);
+    }
+    let mut game_boy = test_rom.game_boy();
+    harness::run(&mut game_boy, core::M_CYCLES_PER_FRAME);
+    let registers = game_boy.registers();
+    (registers.b, registers.c, registers.d)
+}
+
+#[test]
+fn multicart_banking() {
+    assert_eq!(run(3), (0x12, 0x10, 0x10));
+    assert_eq!(run(1), (0x12, 0x10, 0x10));
+}
+
+#[test]
+fn regular_banking() {
+    assert_eq!(run(0), (0x22, 0x20, 0x30));
+}
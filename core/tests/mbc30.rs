@@ -0,0 +1,52 @@
+mod harness;
+
+fn run(rom_size: u8, ram_size: u8) -> (u8, u8, u8) {
+    let mut game_boy = harness::test_rom(&[
+        0x3E, 0x0A, // LD A,$0A
+        0xEA, 0x00, 0x00, // LD ($0000),A
+        0x3E, 0x80, // LD A,$80
+        0xEA, 0x00, 0x20, // LD ($2000),A
+        0xFA, 0x00, 0x40, // LD A,($4000)
+        0x47, // LD B,A
+        0x3E, 0x07, // LD A,$07
+        0xEA, 0x00, 0x40, // LD ($4000),A
+        0x3E, 0x77, // LD A,$77
+        0xEA, 0x00, 0xA0, // LD ($A000),A
+        0x3E, 0x03, // LD A,$03
+        0xEA, 0x00, 0x40, // LD ($4000),A
+        0x3E, 0x33, // LD A,$33
+        0xEA, 0x00, 0xA0, // LD ($A000),A
+        0x3E, 0x07, // LD A,$07
+        0xEA, 0x00, 0x40, // LD ($4000),A
+        0xFA, 0x00, 0xA0, // LD A,($A000)
+        0x4F, // LD C,A
+        0x3E, 0x03, // LD A,$03
+        0xEA, 0x00, 0x40, // LD ($4000),A
+        0xFA, 0x00, 0xA0, // LD A,($A000)
+        0x57, // LD D,A
+        0x18, 0xFE, // JR -2
+    ])
+    .cartridge_type(0x13)
+    .rom_size(rom_size)
+    .ram_size(ram_size)
+    .bank_markers()
+    .game_boy();
+    harness::run(&mut game_boy, core::M_CYCLES_PER_FRAME);
+    let registers = game_boy.registers();
+    (registers.b, registers.c, registers.d)
+}
+
+#[test]
+fn mbc30_banking() {
+    assert_eq!(run(0x07, 0x05), (0x80, 0x77, 0x33));
+}
+
+#[test]
+fn mbc30_selected_by_ram_size() {
+    assert_eq!(run(0x06, 0x05).1, 0x77);
+}
+
+#[test]
+fn mbc3_banking() {
+    assert_eq!(run(0x06, 0x03), (0x01, 0xFF, 0x33));
+}
@@ -0,0 +1,103 @@
+mod harness;
+
+use core::{Cartridge, Tilt, TiltScript, TiltSource};
+
+fn cartridge() -> Cartridge {
+    let mut cartridge = harness::test_rom(&[])
+        .cartridge_type(0x22)
+        .rom_size(0x02)
+        .bank_markers()
+        .cartridge();
+    cartridge.write_rom(0x0000, 0x0A);
+    cartridge.write_rom(0x4000, 0x40);
+    cartridge
+}
+
+fn read_latch(cartridge: &Cartridge) -> (u16, u16) {
+    let read = |register: u16| cartridge.read_ram(register << 4) as u16;
+    (read(0x3) << 8 | read(0x2), read(0x5) << 8 | read(0x4))
+}
+
+fn clock_bit(cartridge: &mut Cartridge, bit: u16) -> bool {
+    let data_in = (bit as u8 & 0b1) << 1;
+    cartridge.write_ram(0x0080, 0x80 | data_in);
+    cartridge.write_ram(0x0080, 0xC0 | data_in);
+    cartridge.read_ram(0x0080) & 0b1 != 0
+}
+
+fn send(cartridge: &mut Cartridge, bits: u16, length: usize) {
+    for index in (0..length).rev() {
+        clock_bit(cartridge, bits >> index);
+    }
+}
+
+fn command(cartridge: &mut Cartridge, command: u16) {
+    cartridge.write_ram(0x0080, 0x00);
+    send(cartridge, 0b1, 1);
+    send(cartridge, command, 10);
+}
+
+fn read_word(cartridge: &mut Cartridge, address: u16) -> u16 {
+    command(cartridge, 0b10 << 8 | address);
+    assert_eq!(cartridge.read_ram(0x0080) & 0b1, 0);
+    (0..16).fold(0, |word, _| word << 1 | clock_bit(cartridge, 0) as u16)
+}
+
+fn write_word(cartridge: &mut Cartridge, address: u16, value: u16) {
+    command(cartridge, 0b01 << 8 | address);
+    send(cartridge, value, 16);
+    cartridge.write_ram(0x0080, 0x00);
+}
+
+#[test]
+fn banking() {
+    let mut cartridge = cartridge();
+    cartridge.write_rom(0x2000, 0x06);
+    assert_eq!(cartridge.read_rom(0x4000), 0x06);
+    cartridge.write_rom(0x4000, 0x00);
+    assert_eq!(cartridge.read_ram(0x0020), 0xFF);
+}
+
+#[test]
+fn latches_the_accelerometer() {
+    let mut cartridge = cartridge();
+    cartridge.set_tilt(Tilt::new(0.5, -1.0));
+    assert_eq!(read_latch(&cartridge), (0x8000, 0x8000));
+    cartridge.write_ram(0x0010, 0xAA);
+    assert_eq!(read_latch(&cartridge), (0x8000, 0x8000));
+    cartridge.write_ram(0x0000, 0x55);
+    cartridge.write_ram(0x0010, 0xAA);
+    assert_eq!(read_latch(&cartridge), (0x8208, 0x8160));
+    cartridge.set_tilt(Default::default());
+    cartridge.write_ram(0x0010, 0xAA);
+    assert_eq!(read_latch(&cartridge), (0x8208, 0x8160));
+}
+
+#[test]
+fn reads_and_writes_the_eeprom() {
+    let mut cartridge = cartridge();
+    write_word(&mut cartridge, 0x05, 0xBEEF);
+    assert_eq!(read_word(&mut cartridge, 0x05), 0xFFFF);
+    command(&mut cartridge, 0b00_1100_0000);
+    write_word(&mut cartridge, 0x05, 0xBEEF);
+    assert_eq!(read_word(&mut cartridge, 0x05), 0xBEEF);
+    assert_eq!(cartridge.save_data().unwrap()[0x0A..0x0C], [0xEF, 0xBE]);
+    command(&mut cartridge, 0b11 << 8 | 0x05);
+    assert_eq!(read_word(&mut cartridge, 0x05), 0xFFFF);
+    command(&mut cartridge, 0b00_0100_0000);
+    send(&mut cartridge, 0x1234, 16);
+    assert_eq!(read_word(&mut cartridge, 0x7F), 0x1234);
+    command(&mut cartridge, 0b00_0000_0000);
+    command(&mut cartridge, 0b00_1000_0000);
+    assert_eq!(read_word(&mut cartridge, 0x00), 0x1234);
+}
+
+#[test]
+fn parses_tilt_scripts() {
+    assert_eq!("0.5, -2".parse(), Ok(Tilt::new(0.5, -1.0)));
+    assert!("0.5".parse::<Tilt>().is_err());
+    let mut script: TiltScript = "# frame x,y\n60 1,0\n0 0,0.5\n".parse().unwrap();
+    assert_eq!(script.tilt(30), Tilt::new(0.0, 0.5));
+    assert_eq!(script.tilt(60), Tilt::new(1.0, 0.0));
+    assert!("60 sideways".parse::<TiltScript>().is_err());
+}
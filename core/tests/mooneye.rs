@@ -0,0 +1,35 @@
+mod harness;
+
+macro_rules! mooneye_tests {
+    ($($name: ident: $path: literal,)*) => {
+        $(
+            #[test]
+            #[ignore = "needs the test ROMs, see UUGB_TEST_ROMS"]
+            fn $name() {
+                harness::run_mooneye($path);
+            }
+        )*
+    };
+}
+
+mooneye_tests! {
+    bits_mem_oam: "mooneye/acceptance/bits/mem_oam.gb",
+    bits_reg_f: "mooneye/acceptance/bits/reg_f.gb",
+    bits_unused_hwio_gs: "mooneye/acceptance/bits/unused_hwio-GS.gb",
+    instr_daa: "mooneye/acceptance/instr/daa.gb",
+    interrupts_ie_push: "mooneye/acceptance/interrupts/ie_push.gb",
+    oam_dma_basic: "mooneye/acceptance/oam_dma/basic.gb",
+    oam_dma_reg_read: "mooneye/acceptance/oam_dma/reg_read.gb",
+    timer_div_write: "mooneye/acceptance/timer/div_write.gb",
+    timer_tim00: "mooneye/acceptance/timer/tim00.gb",
+    timer_tim01: "mooneye/acceptance/timer/tim01.gb",
+    timer_tim10: "mooneye/acceptance/timer/tim10.gb",
+    timer_tim11: "mooneye/acceptance/timer/tim11.gb",
+    mbc1_bits_bank1: "mooneye/emulator-only/mbc1/bits_bank1.gb",
+    mbc1_bits_bank2: "mooneye/emulator-only/mbc1/bits_bank2.gb",
+    mbc1_bits_mode: "mooneye/emulator-only/mbc1/bits_mode.gb",
+    mbc1_bits_ramg: "mooneye/emulator-only/mbc1/bits_ramg.gb",
+    mbc1_rom_512kb: "mooneye/emulator-only/mbc1/rom_512kb.gb",
+    mbc1_multicart_rom_8mb: "mooneye/emulator-only/mbc1/multicart_rom_8Mb.gb",
+    mbc5_rom_512kb: "mooneye/emulator-only/mbc5/rom_512kb.gb",
+}
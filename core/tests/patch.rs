@@ -0,0 +1,151 @@
+use core::{apply_patch, crc32, find_patch, Header, PatchError};
+
+fn number(mut value: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let data = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(0x80 | data);
+            return bytes;
+        }
+        bytes.push(data);
+        value -= 1;
+    }
+}
+
+fn finish(mut patch: Vec<u8>, source: &[u8], target: &[u8]) -> Vec<u8> {
+    patch.extend(crc32(source).to_le_bytes());
+    patch.extend(crc32(target).to_le_bytes());
+    patch.extend(crc32(&patch).to_le_bytes());
+    patch
+}
+
+fn source() -> Vec<u8> {
+    let mut rom: Vec<u8> = (0..0x8000).map(|index| (index % 0xFD) as u8).collect();
+    rom[0x0147] = 0x00;
+    rom
+}
+
+fn translated() -> Vec<u8> {
+    let mut rom = source();
+    rom[0x0134..0x0139].copy_from_slice(b"HELLO");
+    rom[0x4000..0x4010].fill(0xAA);
+    rom
+}
+
+#[test]
+fn crc32_check_value() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn applies_ips() {
+    let mut patch = b"PATCH".to_vec();
+    patch.extend([0x00, 0x01, 0x34, 0x00, 0x05]);
+    patch.extend(b"HELLO");
+    patch.extend([0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x10, 0xAA]);
+    patch.extend(b"EOF");
+    assert_eq!(apply_patch(&source(), &patch), Ok(translated()));
+    let mut expanded = patch.clone();
+    expanded.truncate(expanded.len() - 3);
+    expanded.extend([0x00, 0x80, 0x00, 0x00, 0x02, 0x12, 0x34]);
+    expanded.extend(b"EOF");
+    let rom = apply_patch(&source(), &expanded).unwrap();
+    assert_eq!(rom.len(), 0x8002);
+    assert_eq!(rom[0x8000..], [0x12, 0x34]);
+    let mut truncated = patch;
+    truncated.extend([0x00, 0x40, 0x00]);
+    assert_eq!(apply_patch(&source(), &truncated).unwrap().len(), 0x4000);
+    assert_eq!(
+        apply_patch(&source(), b"PATCH\x00\x01"),
+        Err(PatchError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn applies_ups() {
+    let (source, target) = (source(), translated());
+    let mut patch = b"UPS1".to_vec();
+    patch.extend(number(source.len()));
+    patch.extend(number(target.len()));
+    let mut position = 0;
+    for (start, end) in [(0x0134, 0x0139), (0x4000, 0x4010)] {
+        patch.extend(number(start - position));
+        patch.extend((start..end).map(|index| source[index] ^ target[index]));
+        patch.push(0x00);
+        position = end + 1;
+    }
+    let patch = finish(patch, &source, &target);
+    assert_eq!(apply_patch(&source, &patch), Ok(target.clone()));
+    assert_eq!(
+        apply_patch(&target, &patch),
+        Err(PatchError::SourceChecksumMismatch)
+    );
+    let mut corrupted = patch;
+    corrupted[8] ^= 0xFF;
+    assert_eq!(
+        apply_patch(&source, &corrupted),
+        Err(PatchError::PatchChecksumMismatch)
+    );
+}
+
+#[test]
+fn applies_bps() {
+    let source = source();
+    let mut target = translated();
+    target.extend_from_slice(&source[0x0010..0x0014]);
+    target.extend_from_slice(b"HELLO");
+    let action = |command: usize, length: usize| number((length - 1) << 2 | command);
+    let mut patch = b"BPS1".to_vec();
+    patch.extend(number(source.len()));
+    patch.extend(number(target.len()));
+    patch.extend(number(4));
+    patch.extend(b"meta");
+    patch.extend(action(0, 0x0134));
+    patch.extend(action(1, 5));
+    patch.extend(b"HELLO");
+    patch.extend(action(0, 0x4000 - 0x0139));
+    patch.extend(action(1, 0x10));
+    patch.extend([0xAA; 0x10]);
+    patch.extend(action(0, 0x8000 - 0x4010));
+    patch.extend(action(2, 4));
+    patch.extend(number(0x10 << 1));
+    patch.extend(action(3, 5));
+    patch.extend(number(0x0134 << 1));
+    let patch = finish(patch, &source, &target);
+    assert_eq!(apply_patch(&source, &patch), Ok(target.clone()));
+    let original = Header::load(&source).unwrap();
+    let patched = Header::load(&apply_patch(&source, &patch).unwrap()).unwrap();
+    assert_ne!(
+        patched.header_checksum.calculated_value(),
+        original.header_checksum.calculated_value()
+    );
+    assert_ne!(
+        patched.global_checksum.calculated_value(),
+        original.global_checksum.calculated_value()
+    );
+    assert_eq!(
+        apply_patch(&target, &patch),
+        Err(PatchError::SourceChecksumMismatch)
+    );
+}
+
+#[test]
+fn rejects_unknown_formats() {
+    assert_eq!(
+        apply_patch(&source(), b"NOT A PATCH"),
+        Err(PatchError::UnknownFormat)
+    );
+}
+
+#[test]
+fn finds_patches_next_to_the_rom() {
+    let directory = std::env::temp_dir().join(format!("uugb-patch-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let rom_path = directory.join("game.gb");
+    assert_eq!(find_patch(&rom_path), None);
+    std::fs::write(directory.join("game.bps"), b"").unwrap();
+    assert_eq!(find_patch(&rom_path), Some(directory.join("game.bps")));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
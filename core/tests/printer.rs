@@ -0,0 +1,94 @@
+use core::{Color, PrintedImage, Printer, SerialConnection};
+
+fn transfer(printer: &mut Printer, value: u8) -> u8 {
+    (0..8).rev().fold(0, |received, bit| {
+        printer.receive(value >> bit & 1 != 0);
+        received << 1 | printer.send() as u8
+    })
+}
+
+fn send_packet(printer: &mut Printer, command: u8, is_compressed: bool, data: &[u8]) -> (u8, u8) {
+    let mut bytes = vec![command, is_compressed as u8];
+    bytes.extend((data.len() as u16).to_le_bytes());
+    bytes.extend(data);
+    let checksum = bytes
+        .iter()
+        .fold(0u16, |sum, byte| sum.wrapping_add(*byte as u16));
+    for byte in [0x88, 0x33]
+        .iter()
+        .chain(&bytes)
+        .chain(&checksum.to_le_bytes())
+    {
+        assert_eq!(transfer(printer, *byte), 0x00);
+    }
+    (transfer(printer, 0x00), transfer(printer, 0x00))
+}
+
+fn band(tile: [u8; 16]) -> Vec<u8> {
+    tile.iter().copied().cycle().take(16 * 40).collect()
+}
+
+#[test]
+fn prints_a_band() {
+    let mut printer = Printer::default();
+    assert_eq!(send_packet(&mut printer, 0x01, false, &[]), (0x81, 0x00));
+    let tile = [
+        0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00,
+        0x00,
+    ];
+    assert_eq!(
+        send_packet(&mut printer, 0x04, false, &band(tile)),
+        (0x81, 0x08)
+    );
+    assert_eq!(send_packet(&mut printer, 0x04, false, &[]), (0x81, 0x08));
+    assert_eq!(
+        send_packet(&mut printer, 0x02, false, &[0x01, 0x13, 0xE4, 0x40]),
+        (0x81, 0x06)
+    );
+    assert_eq!(send_packet(&mut printer, 0x0F, false, &[]), (0x81, 0x06));
+    let images = printer.take_images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].height(), 16);
+    let column: Vec<Color> = (0..4)
+        .map(|y| images[0].pixels()[y * PrintedImage::WIDTH])
+        .collect();
+    assert_eq!(
+        column,
+        [
+            Color::LightGray,
+            Color::DarkGray,
+            Color::Black,
+            Color::White
+        ]
+    );
+}
+
+#[test]
+fn decompresses_data() {
+    let mut printer = Printer::default();
+    let compressed = [
+        0xFE, 0xFF, 0xFE, 0xFF, 0xFE, 0xFF, 0xFE, 0xFF, 0xFE, 0x00, 0x01, 0xFF, 0x00,
+    ];
+    assert_eq!(
+        send_packet(&mut printer, 0x04, true, &compressed),
+        (0x81, 0x08)
+    );
+    send_packet(&mut printer, 0x02, false, &[0x01, 0x01, 0xE4, 0x40]);
+    let images = printer.take_images();
+    assert_eq!(images[0].height(), 16);
+    assert_eq!(images[0].pixels()[0], Color::Black);
+    assert_eq!(
+        images[0].pixels()[PrintedImage::WIDTH * 15 + 159],
+        Color::White
+    );
+}
+
+#[test]
+fn reports_checksum_errors() {
+    let mut printer = Printer::default();
+    for byte in [0x88, 0x33, 0x0F, 0x00, 0x00, 0x00, 0x00, 0x00] {
+        transfer(&mut printer, byte);
+    }
+    assert_eq!(transfer(&mut printer, 0x00), 0x81);
+    assert_eq!(transfer(&mut printer, 0x00), 0x01);
+}
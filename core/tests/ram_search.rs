@@ -0,0 +1,105 @@
+mod harness;
+
+use core::{GameBoy, RamAddress, RamSearch, RamSearchFilter, RamSearchValueType};
+
+fn game_boy() -> GameBoy {
+    let mut game_boy = harness::test_rom(&[
+        0x21, 0x00, 0xC1, // LD HL,$C100
+        0x36, 0x05, // LD (HL),$05
+        0x2C, // INC L
+        0x36, 0x01, // LD (HL),$01
+        0x18, 0xFE, // JR -2
+    ])
+    .cartridge_type(0x03)
+    .ram_size(0x03)
+    .game_boy();
+    run_frames(&mut game_boy, 1);
+    game_boy
+}
+
+fn run_frames(game_boy: &mut GameBoy, frames: u64) {
+    harness::run(game_boy, frames * core::M_CYCLES_PER_FRAME);
+}
+
+fn addresses(ram_search: &RamSearch, game_boy: &mut GameBoy) -> Vec<RamAddress> {
+    ram_search
+        .results(game_boy, usize::MAX)
+        .iter()
+        .map(|result| result.address)
+        .collect()
+}
+
+fn bus_address(address: u16) -> RamAddress {
+    RamAddress {
+        bank: None,
+        address,
+    }
+}
+
+#[test]
+fn parses_filters() {
+    for (text, filter) in [
+        ("=", RamSearchFilter::Unchanged),
+        ("!=", RamSearchFilter::Changed),
+        ("<", RamSearchFilter::Decreased),
+        ("> ", RamSearchFilter::Increased),
+        ("=3F", RamSearchFilter::EqualTo(0x3F)),
+        ("< $10", RamSearchFilter::LessThan(0x10)),
+        ("+1", RamSearchFilter::IncreasedBy(0x01)),
+        ("-A", RamSearchFilter::DecreasedBy(0x0A)),
+    ] {
+        assert_eq!(text.parse::<RamSearchFilter>(), Ok(filter));
+    }
+    assert!("1".parse::<RamSearchFilter>().is_err());
+    assert!("+".parse::<RamSearchFilter>().is_err());
+    assert!("=G".parse::<RamSearchFilter>().is_err());
+}
+
+#[test]
+fn narrows_bytes() {
+    let mut game_boy = game_boy();
+    let mut ram_search = RamSearch::new(&mut game_boy, RamSearchValueType::Byte);
+    assert_eq!(ram_search.len(), 0x10000 + 0x8000);
+    ram_search.filter(&mut game_boy, RamSearchFilter::EqualTo(0x05));
+    assert!(addresses(&ram_search, &mut game_boy).contains(&bus_address(0xC100)));
+    game_boy.add_cheat("010A00C1".parse().unwrap());
+    run_frames(&mut game_boy, 2);
+    ram_search.filter(&mut game_boy, RamSearchFilter::IncreasedBy(0x05));
+    assert_eq!(
+        addresses(&ram_search, &mut game_boy),
+        [bus_address(0xC100), bus_address(0xE100)]
+    );
+    ram_search.filter(&mut game_boy, RamSearchFilter::Unchanged);
+    let results = ram_search.results(&mut game_boy, 1);
+    assert_eq!((results[0].previous, results[0].current), (0x0A, 0x0A));
+}
+
+#[test]
+fn reads_words() {
+    let mut game_boy = game_boy();
+    for (value_type, value) in [
+        (RamSearchValueType::WordLittleEndian, 0x0105),
+        (RamSearchValueType::WordBigEndian, 0x0501),
+    ] {
+        let mut ram_search = RamSearch::new(&mut game_boy, value_type);
+        assert_eq!(ram_search.len(), 0xFFFF + 0x7FFF);
+        ram_search.filter(&mut game_boy, RamSearchFilter::EqualTo(value));
+        assert!(addresses(&ram_search, &mut game_boy).contains(&bus_address(0xC100)));
+    }
+}
+
+#[test]
+fn searches_banked_cartridge_ram() {
+    let mut game_boy = game_boy();
+    let mut ram_search = RamSearch::new(&mut game_boy, RamSearchValueType::Byte);
+    game_boy.add_cheat("827704A0".parse().unwrap());
+    run_frames(&mut game_boy, 2);
+    ram_search.filter(&mut game_boy, RamSearchFilter::EqualTo(0x77));
+    ram_search.filter(&mut game_boy, RamSearchFilter::Unchanged);
+    let address = RamAddress {
+        bank: Some(2),
+        address: 0xA004,
+    };
+    assert!(addresses(&ram_search, &mut game_boy).contains(&address));
+    assert_eq!(address.to_string(), "02:A004");
+}
@@ -0,0 +1,140 @@
+mod harness;
+
+use core::{Cartridge, GameBoy, RtcClock};
+
+const RAM_SIZE: usize = 0x8000;
+
+fn cartridge(clock: RtcClock) -> Cartridge {
+    let mut cartridge = harness::test_rom(&[0x18, 0xFE])
+        .cartridge_type(0x10)
+        .ram_size(0x03)
+        .cartridge();
+    cartridge.set_rtc_clock(clock);
+    cartridge
+}
+
+fn read_rtc(cartridge: &mut Cartridge) -> [u8; 5] {
+    cartridge.write_rom(0x0000, 0x0A);
+    cartridge.write_rom(0x6000, 0x00);
+    cartridge.write_rom(0x6000, 0x01);
+    let mut registers = [0; 5];
+    for (register, select) in registers.iter_mut().zip(0x08..) {
+        cartridge.write_rom(0x4000, select);
+        *register = cartridge.read_ram(0x0000);
+    }
+    registers
+}
+
+fn write_rtc(cartridge: &mut Cartridge, registers: [u8; 5]) {
+    cartridge.write_rom(0x0000, 0x0A);
+    for (register, select) in registers.into_iter().zip(0x08..) {
+        cartridge.write_rom(0x4000, select);
+        cartridge.write_ram(0x0000, register);
+    }
+}
+
+fn save_data(registers: [u8; 5], timestamp: i64) -> Vec<u8> {
+    let mut data = vec![0x00; RAM_SIZE];
+    for _ in 0..2 {
+        data.extend(
+            registers
+                .iter()
+                .flat_map(|value| (*value as u32).to_le_bytes()),
+        );
+    }
+    data.extend(timestamp.to_le_bytes());
+    data
+}
+
+#[test]
+fn parses_clocks() {
+    assert_eq!("real".parse(), Ok(RtcClock::Real));
+    assert_eq!("Emulated".parse(), Ok(RtcClock::Emulated));
+    assert_eq!("1700000000".parse(), Ok(RtcClock::Fixed(1_700_000_000)));
+    assert!("soon".parse::<RtcClock>().is_err());
+    assert_eq!(RtcClock::Fixed(42).to_string(), "42");
+}
+
+#[test]
+fn counts_emulated_cycles() {
+    let mut game_boy = GameBoy::new(cartridge(RtcClock::Emulated));
+    harness::run(&mut game_boy, core::M_CYCLES);
+    assert_eq!(read_rtc(game_boy.cartridge_mut()), [1, 0, 0, 0, 0]);
+}
+
+#[test]
+fn rolls_over_into_the_day_carry() {
+    let mut cartridge = cartridge(RtcClock::Emulated);
+    write_rtc(&mut cartridge, [59, 59, 23, 0xFF, 0x01]);
+    for _ in 0..core::M_CYCLES {
+        cartridge.tick();
+    }
+    assert_eq!(read_rtc(&mut cartridge), [0, 0, 0, 0x00, 0x80]);
+    write_rtc(&mut cartridge, [63, 0, 0, 0, 0x00]);
+    for _ in 0..core::M_CYCLES {
+        cartridge.tick();
+    }
+    assert_eq!(read_rtc(&mut cartridge), [0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn stops_while_halted() {
+    let mut cartridge = cartridge(RtcClock::Emulated);
+    write_rtc(&mut cartridge, [10, 0, 0, 0, 0x40]);
+    for _ in 0..core::M_CYCLES {
+        cartridge.tick();
+    }
+    assert_eq!(read_rtc(&mut cartridge), [10, 0, 0, 0, 0x40]);
+}
+
+#[test]
+fn catches_up_on_elapsed_time() {
+    let mut cartridge = cartridge(RtcClock::Fixed(1_000_000));
+    let elapsed = 2 * 86400 + 3 * 3600 + 4 * 60 + 5;
+    cartridge.load_save_data(&save_data([0, 0, 0, 0xFF, 0x00], 1_000_000 - elapsed));
+    assert_eq!(read_rtc(&mut cartridge), [5, 4, 3, 0x01, 0x01]);
+    let mut emulated = self::cartridge(RtcClock::Emulated);
+    emulated.load_save_data(&save_data([30, 0, 0, 0, 0x00], 1_000_000 - elapsed));
+    assert_eq!(read_rtc(&mut emulated), [30, 0, 0, 0, 0x00]);
+}
+
+#[test]
+fn persists_registers_in_save_data() {
+    let mut cartridge = cartridge(RtcClock::Fixed(1_000_000));
+    write_rtc(&mut cartridge, [1, 2, 3, 4, 0x00]);
+    let data = cartridge.save_data().unwrap();
+    assert_eq!(data.len(), RAM_SIZE + 48);
+    assert_eq!(
+        data[RAM_SIZE..RAM_SIZE + 20],
+        save_data([1, 2, 3, 4, 0x00], 0)[RAM_SIZE..RAM_SIZE + 20]
+    );
+    assert_eq!(data[RAM_SIZE + 40..], 1_000_000i64.to_le_bytes());
+    let mut restored = self::cartridge(RtcClock::Fixed(1_000_060));
+    restored.load_save_data(&data);
+    assert_eq!(read_rtc(&mut restored), [1, 3, 3, 4, 0x00]);
+}
+
+#[test]
+fn emulated_footer_starts_at_zero() {
+    let mut cartridge = cartridge(RtcClock::Emulated);
+    for _ in 0..core::M_CYCLES {
+        cartridge.tick();
+    }
+    let data = cartridge.save_data().unwrap();
+    assert_eq!(data[RAM_SIZE + 40..], 1i64.to_le_bytes());
+}
+
+#[test]
+fn mbc3_without_timer_has_no_rtc() {
+    let mut cartridge = harness::test_rom(&[0x18, 0xFE])
+        .cartridge_type(0x13)
+        .ram_size(0x03)
+        .cartridge();
+    cartridge.set_rtc_clock(RtcClock::Emulated);
+    write_rtc(&mut cartridge, [1, 2, 3, 4, 0x00]);
+    for _ in 0..core::M_CYCLES {
+        cartridge.tick();
+    }
+    assert_eq!(read_rtc(&mut cartridge), [0xFF; 5]);
+    assert_eq!(cartridge.save_data().unwrap().len(), RAM_SIZE);
+}
@@ -0,0 +1,51 @@
+mod harness;
+
+use core::{GameBoy, Model, SaveStateError};
+
+const M_CYCLES: u64 = 20_000;
+
+fn running_game_boy(model: Model) -> GameBoy {
+    let cartridge = harness::test_rom(&[
+        0x21, 0x00, 0xC0, // LD HL,$C000
+        0x34, // INC (HL)
+        0x18, 0xFD, // JR -3
+    ])
+    .cartridge();
+    let mut game_boy = GameBoy::builder(cartridge).model(model).build();
+    harness::run(&mut game_boy, M_CYCLES);
+    game_boy
+}
+
+#[test]
+fn round_trip() {
+    let mut game_boy = running_game_boy(Model::Dmg);
+    let state = game_boy.save_state();
+    let dump = game_boy.dump();
+    harness::run(&mut game_boy, M_CYCLES);
+    let next_dump = game_boy.dump();
+    assert_ne!(next_dump, dump);
+    game_boy.load_state(&state).unwrap();
+    assert_eq!(game_boy.dump(), dump);
+    harness::run(&mut game_boy, M_CYCLES);
+    assert_eq!(game_boy.dump(), next_dump);
+}
+
+#[test]
+fn rejected_state_leaves_the_emulator_unchanged() {
+    let mut game_boy = running_game_boy(Model::Dmg);
+    let dump = game_boy.dump();
+    let mut other = running_game_boy(Model::Dmg);
+    harness::run(&mut other, M_CYCLES);
+    let mut truncated = other.save_state();
+    truncated.pop();
+    assert_eq!(
+        game_boy.load_state(&truncated),
+        Err(SaveStateError::UnexpectedEnd)
+    );
+    assert_eq!(game_boy.dump(), dump);
+    assert_eq!(
+        game_boy.load_state(&running_game_boy(Model::Cgb).save_state()),
+        Err(SaveStateError::ModelMismatch)
+    );
+    assert_eq!(game_boy.dump(), dump);
+}
@@ -0,0 +1,63 @@
+mod harness;
+
+use core::{BufferedSerialConnection, GameBoy, NoAudioTerminal, NoRenderer};
+
+const M_CYCLES: u64 = 10_000;
+
+fn game_boy() -> GameBoy {
+    harness::test_rom(&[
+        0x11, 0x00, 0x02, // LD DE,$0200
+        0x21, 0x00, 0xC0, // LD HL,$C000
+        0x1A, // LD A,(DE)
+        0x13, // INC DE
+        0xE0, 0x01, // LDH (SB),A
+        0x3E, 0x81, // LD A,$81
+        0xE0, 0x02, // LDH (SC),A
+        0xF0, 0x02, // LDH A,(SC)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,-6
+        0xF0, 0x01, // LDH A,(SB)
+        0x22, // LD (HL+),A
+        0x7D, // LD A,L
+        0xFE, 0x02, // CP $02
+        0x20, 0xEA, // JR NZ,-22
+        0x18, 0xFE, // JR -2
+    ])
+    .data(0x0200, &[0x81, 0x3C])
+    .game_boy()
+}
+
+fn run(game_boy: &mut GameBoy, serial_connection: &mut BufferedSerialConnection) {
+    for _ in 0..M_CYCLES {
+        game_boy.tick(&mut NoRenderer, &mut NoAudioTerminal, serial_connection);
+    }
+}
+
+fn transfer() -> (GameBoy, BufferedSerialConnection) {
+    let mut game_boy = game_boy();
+    let mut serial_connection = BufferedSerialConnection::default();
+    serial_connection.push(&[0xA5, 0x01]);
+    run(&mut game_boy, &mut serial_connection);
+    (game_boy, serial_connection)
+}
+
+#[test]
+fn buffered_serial_connection() {
+    let (mut game_boy, serial_connection) = transfer();
+    let received = serial_connection.buffer();
+    assert_eq!(
+        received.iter().map(|byte| byte.value).collect::<Vec<_>>(),
+        [0x81, 0x3C]
+    );
+    assert_eq!(game_boy.read(0xC000), 0xA5);
+    assert_eq!(game_boy.read(0xC001), 0x01);
+    assert!(0 < received[0].m_cycle && received[0].m_cycle < received[1].m_cycle);
+    assert!(received[1].m_cycle < M_CYCLES);
+}
+
+#[test]
+fn received_bytes_are_stamped_with_emulated_time() {
+    let (_, first) = transfer();
+    let (_, second) = transfer();
+    assert_eq!(first.buffer(), second.buffer());
+}
@@ -0,0 +1,74 @@
+mod harness;
+
+use core::{Disassembler, Symbols, SymbolsError};
+
+const SYMBOLS: &str = "\
+; RGBDS symbols
+00:0150 Main
+00:0158 Main.loop ; local label
+01:4000 Far
+02:4000 Other
+00:C000 wCounter
+";
+
+fn symbols() -> Symbols {
+    SYMBOLS.parse().unwrap()
+}
+
+#[test]
+fn parse() {
+    let symbols = symbols();
+    assert_eq!(symbols.len(), 5);
+    assert_eq!(symbols.address("Main.loop"), Some((0, 0x0158)));
+    assert_eq!(symbols.address("Far"), Some((1, 0x4000)));
+    assert_eq!(symbols.address("Missing"), None);
+    assert_eq!(symbols.label(Some(2), 0x4000), Some("Other"));
+    assert_eq!(symbols.label(Some(0), 0x4000), None);
+    assert_eq!(symbols.label(None, 0xC000), Some("wCounter"));
+    assert!("".parse::<Symbols>().unwrap().is_empty());
+    assert_eq!(
+        "00:0150 Main\n0150 Main".parse::<Symbols>(),
+        Err(SymbolsError::InvalidLine(2, "0150 Main".into()))
+    );
+    assert_eq!(
+        "XX:0150 Main".parse::<Symbols>(),
+        Err(SymbolsError::InvalidLine(1, "XX:0150 Main".into()))
+    );
+}
+
+#[test]
+fn describe() {
+    let symbols = symbols();
+    assert_eq!(symbols.describe(Some(0), 0x0150), Some("Main".into()));
+    assert_eq!(symbols.describe(Some(0), 0x0153), Some("Main+$3".into()));
+    assert_eq!(
+        symbols.describe(Some(0), 0x015A),
+        Some("Main.loop+$2".into())
+    );
+    assert_eq!(symbols.describe(Some(1), 0x4010), Some("Far+$10".into()));
+    assert_eq!(symbols.describe(Some(2), 0x4010), Some("Other+$10".into()));
+    assert_eq!(symbols.describe(Some(0), 0x0100), None);
+    assert_eq!(symbols.describe(Some(0), 0x4000), None);
+    assert_eq!(symbols.describe(None, 0xC0FF), Some("wCounter+$FF".into()));
+    assert_eq!(symbols.describe(None, 0xD000), None);
+}
+
+#[test]
+fn disassembly() {
+    let rom = harness::test_rom(&[
+        0xC3, 0x50, 0x01, // JP $0150
+        0x18, 0xFE, // JR -2
+        0xCD, 0x00, 0x40, // CALL $4000
+        0x20, 0x03, // JR NZ,+3
+        0xFA, 0x00, 0xC0, // LD A,($C000)
+    ])
+    .build();
+    let symbols = SYMBOLS.replace("02:4000 Other\n", "").parse().unwrap();
+    let disassembler = Disassembler::new(&rom).symbols(&symbols);
+    let text = |address| disassembler.disassemble(0, address).unwrap().text;
+    assert_eq!(text(0x0150), "JP Main");
+    assert_eq!(text(0x0153), "JR Main+$3");
+    assert_eq!(text(0x0155), "CALL Far");
+    assert_eq!(text(0x0158), "JR NZ, Main.loop+$5");
+    assert_eq!(text(0x015A), "LD A, (wCounter)");
+}
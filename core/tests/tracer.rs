@@ -0,0 +1,93 @@
+mod harness;
+
+use core::{TraceFilter, Tracer};
+use std::{cell::RefCell, io, rc::Rc};
+
+const M_CYCLES: u64 = 1_000;
+
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn trace(filter: TraceFilter) -> Vec<String> {
+    let mut game_boy = harness::test_rom(&[
+        0x3E, 0x02, // LD A,$02
+        0xEA, 0x00, 0x20, // LD ($2000),A
+        0xCD, 0x00, 0x40, // CALL $4000
+    ])
+    .cartridge_type(0x01)
+    .rom_size(0x01)
+    .data(0x014D, &[0x01])
+    .data(
+        2 * 0x4000,
+        &[
+            0x00, // NOP
+            0x18, 0xFE, // JR -2
+        ],
+    )
+    .game_boy();
+    let buffer = SharedBuffer::default();
+    game_boy.set_tracer(Some(Tracer::new(buffer.clone(), filter)));
+    harness::run(&mut game_boy, M_CYCLES);
+    game_boy.flush_tracer();
+    let text = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+    text.lines().map(String::from).collect()
+}
+
+fn pcs(lines: &[String]) -> Vec<u16> {
+    lines
+        .iter()
+        .map(|line| {
+            let (_, pc) = line.split_once("PC:").unwrap();
+            u16::from_str_radix(&pc[..4], 16).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn gameboy_doctor_format() {
+    let lines = trace(Default::default());
+    assert_eq!(
+        lines[..3],
+        [
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,50,01",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0101 PCMEM:C3,50,01,00",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0150 PCMEM:3E,02,EA,00",
+        ]
+    );
+}
+
+#[test]
+fn pc_range_filter() {
+    let lines = trace(TraceFilter {
+        pc_range: Some(0x0150..=0x0154),
+        bank: None,
+    });
+    assert_eq!(pcs(&lines), [0x0150, 0x0152]);
+}
+
+#[test]
+fn bank_filter() {
+    let lines = trace(TraceFilter {
+        pc_range: None,
+        bank: Some(2),
+    });
+    let pcs = pcs(&lines);
+    assert_eq!(pcs[..3], [0x4000, 0x4001, 0x4001]);
+    assert!(pcs.iter().all(|pc| (0x4000..=0x4001).contains(pc)));
+    assert!(trace(TraceFilter {
+        pc_range: None,
+        bank: Some(1),
+    })
+    .is_empty());
+}
@@ -0,0 +1,122 @@
+mod harness;
+
+use core::{
+    Access, AccessKind, Debugger, DebuggerCommand, DebuggerEvent, GameBoy, NoAudioTerminal,
+    NoRenderer, NoSerialConnection, Watchpoint,
+};
+use std::{cell::RefCell, rc::Rc};
+
+const M_CYCLES: u64 = 1_000;
+
+fn game_boy() -> GameBoy {
+    harness::game_boy(&[
+        0x3E, 0x01, // LD A,$01
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x3E, 0x00, // LD A,$00
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0xFA, 0x00, 0xC0, // LD A,($C000)
+        0x18, 0xFE, // JR -2
+    ])
+}
+
+fn accesses(watchpoint: &str) -> Vec<Access> {
+    let mut game_boy = game_boy();
+    let accesses = Rc::new(RefCell::new(Vec::new()));
+    let hook_accesses = accesses.clone();
+    let watcher = game_boy.watcher_mut();
+    watcher.add_watchpoint(watchpoint.parse().unwrap());
+    watcher.set_hook(move |access| hook_accesses.borrow_mut().push(*access));
+    harness::run(&mut game_boy, M_CYCLES);
+    let accesses = accesses.borrow().clone();
+    accesses
+}
+
+fn access(kind: AccessKind, address: u16, value: u8) -> Access {
+    Access {
+        kind,
+        address,
+        value,
+    }
+}
+
+#[test]
+fn parse_watchpoint() {
+    assert_eq!(
+        "C000-C0FF w =00".parse(),
+        Ok(Watchpoint {
+            start: 0xC000,
+            end: 0xC0FF,
+            kind: Some(AccessKind::Write),
+            value: Some(0x00),
+        })
+    );
+    assert_eq!(
+        "$FF40 r".parse(),
+        Ok(Watchpoint {
+            kind: Some(AccessKind::Read),
+            ..Watchpoint::new(0xFF40)
+        })
+    );
+    assert_eq!("C000 rw".parse(), Ok(Watchpoint::new(0xC000)));
+    assert!("C0FF-C000".parse::<Watchpoint>().is_err());
+    assert!("C000 x".parse::<Watchpoint>().is_err());
+    assert_eq!(
+        "C000-C0FF w =00".parse::<Watchpoint>().unwrap().to_string(),
+        "C000-C0FF w =00"
+    );
+}
+
+#[test]
+fn access_kind() {
+    assert_eq!(
+        accesses("C000-C001 w"),
+        [
+            access(AccessKind::Write, 0xC000, 0x01),
+            access(AccessKind::Write, 0xC001, 0x00),
+        ]
+    );
+    assert_eq!(
+        accesses("C000-C001 r"),
+        [access(AccessKind::Read, 0xC000, 0x01)]
+    );
+    assert_eq!(accesses("C000-C001").len(), 3);
+}
+
+#[test]
+fn value_filter() {
+    assert_eq!(
+        accesses("C000-C001 =00"),
+        [access(AccessKind::Write, 0xC001, 0x00)]
+    );
+    assert_eq!(
+        accesses("C000-C001 r =01"),
+        [access(AccessKind::Read, 0xC000, 0x01)]
+    );
+    assert!(accesses("C000-C001 w =02").is_empty());
+}
+
+#[test]
+fn debugger_pauses_on_watchpoint() {
+    let mut game_boy = game_boy();
+    game_boy
+        .watcher_mut()
+        .add_watchpoint("C001 w".parse().unwrap());
+    let mut debugger = Debugger::default();
+    debugger.resume(&mut game_boy, DebuggerCommand::Continue);
+    let event = (0..M_CYCLES).find_map(|_| {
+        debugger.tick(
+            &mut game_boy,
+            &mut NoRenderer,
+            &mut NoAudioTerminal,
+            &mut NoSerialConnection,
+        )
+    });
+    assert_eq!(
+        event,
+        Some(DebuggerEvent::Watchpoint {
+            access: access(AccessKind::Write, 0xC001, 0x00),
+            pc: 0x0157,
+        })
+    );
+    assert!(debugger.is_paused());
+}
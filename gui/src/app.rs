@@ -1,59 +1,369 @@
-use crate::{audio::AudioOutput, command, renderer::Renderer};
-use core::{Cartridge, GameBoy};
+use crate::{
+    audio::AudioOutput, cheats_window::CheatsWindow, command, debugger_panel::DebuggerPanel,
+    options::Options, printer::PrintedImageWriter, ram_search_window::RamSearchWindow,
+    renderer::Renderer,
+};
+use core::{
+    Cartridge, Debugger, DebuggerCommand, DebuggerEvent, GameBoy, LinkPort, Printer,
+    SerialConnection,
+};
 use eframe::egui;
-use std::rc::Rc;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+const SAVE_DATA_FLUSH_INTERVAL_MS: f64 = 1000.0;
+
+#[cfg(not(target_arch = "wasm32"))]
+type Link = core::SocketConnection;
+
+#[cfg(target_arch = "wasm32")]
+type Link = core::NoSerialConnection;
+
+enum SerialDevice {
+    None,
+    Link(Link),
+    Printer(Printer, PrintedImageWriter),
+}
+
+impl SerialDevice {
+    fn save_printed_images(&mut self) {
+        if let Self::Printer(printer, writer) = self {
+            for image in printer.take_images() {
+                writer.save(&image);
+            }
+        }
+    }
+}
+
+impl SerialConnection for SerialDevice {
+    fn receive(&mut self, bit: bool) {
+        match self {
+            Self::None => {}
+            Self::Link(connection) => connection.receive(bit),
+            Self::Printer(connection, _) => connection.receive(bit),
+        }
+    }
+
+    fn send(&mut self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Link(connection) => connection.send(),
+            Self::Printer(connection, _) => connection.send(),
+        }
+    }
+
+    fn poll_clock(&mut self) -> bool {
+        match self {
+            Self::None => false,
+            Self::Link(connection) => connection.poll_clock(),
+            Self::Printer(connection, _) => connection.poll_clock(),
+        }
+    }
+}
+
+struct Partner {
+    game_boy: GameBoy,
+    renderer: Renderer,
+}
+
+impl Partner {
+    fn new(rom_path: &Path, options: &Options) -> Option<Self> {
+        let rom = fs::read(rom_path)
+            .map_err(|error| {
+                log::warn!(
+                    "Could not read the rom from {}: {:?}",
+                    rom_path.display(),
+                    error
+                )
+            })
+            .ok()?;
+        let rom = patch_rom(&rom, rom_path, None)?;
+        let cartridge = Cartridge::new(Rc::new(rom))
+            .map_err(|error| log::warn!("Could not load the rom: {:?}", error))
+            .ok()?;
+        Some(Self {
+            game_boy: GameBoy::new(cartridge),
+            renderer: Renderer::new(options.palette),
+        })
+    }
+}
 
 struct State {
     game_boy: GameBoy,
+    debugger: Debugger,
     renderer: Renderer,
+    partner: Option<Partner>,
+    controls_partner: bool,
     audio_output: AudioOutput,
     processed_m_cycles: u64,
+    elapsed_m_cycles: u64,
     period_start_time_ms: f64,
+    save_data_file: Option<SaveDataFile>,
+    save_data_flushed_time_ms: f64,
+    save_state_path: Option<PathBuf>,
 }
 
 impl State {
-    pub fn new(rom: Rc<Vec<u8>>) -> Option<Self> {
+    pub fn new(rom: Rc<Vec<u8>>, rom_path: Option<PathBuf>, options: &Options) -> Option<Self> {
+        let mut cartridge = Cartridge::new(rom)
+            .map_err(|error| {
+                log::warn!("Could not load the rom: {:?}", error);
+                error
+            })
+            .ok()?;
+        let mut save_data_file = rom_path
+            .as_ref()
+            .filter(|_| cartridge.has_battery())
+            .map(|rom_path| SaveDataFile::new(rom_path));
+        cartridge.set_rtc_clock(options.rtc_clock);
+        if let Some(data) = save_data_file.as_mut().and_then(|file| file.load()) {
+            cartridge.load_save_data(&data);
+        }
+        let mut game_boy = GameBoy::new(cartridge);
+        if let Some(symbols) = rom_path.as_deref().and_then(load_symbols) {
+            game_boy.set_symbols(symbols);
+        }
+        for cheat in options.cheats.iter() {
+            game_boy.add_cheat(cheat.clone());
+        }
+        let mut debugger = Debugger::default();
+        debugger.resume(&mut game_boy, DebuggerCommand::Continue);
         Some(Self {
-            game_boy: GameBoy::new(
-                Cartridge::new(rom)
-                    .map_err(|error| {
-                        log::warn!("Could not load the rom: {:?}", error);
-                        error
-                    })
-                    .ok()?,
-            ),
-            renderer: Default::default(),
-            audio_output: Default::default(),
+            game_boy,
+            debugger,
+            renderer: Renderer::new(options.palette),
+            partner: options
+                .link_rom_path
+                .as_deref()
+                .and_then(|rom_path| Partner::new(rom_path, options)),
+            controls_partner: false,
+            audio_output: if options.is_muted {
+                AudioOutput::None
+            } else {
+                Default::default()
+            },
             processed_m_cycles: 0,
+            elapsed_m_cycles: 0,
             period_start_time_ms: instant::now(),
+            save_data_file,
+            save_data_flushed_time_ms: instant::now(),
+            save_state_path: rom_path.map(|rom_path| rom_path.with_extension("state")),
         })
     }
 
-    fn advance_cycles(&mut self, button_state: core::ButtonState) {
-        self.game_boy.set_button_state(button_state);
+    fn save_state(&self) {
+        if let Some(path) = &self.save_state_path {
+            if let Err(error) = fs::write(path, self.game_boy.save_state()) {
+                log::warn!(
+                    "Could not write the state to {}: {:?}",
+                    path.display(),
+                    error
+                );
+            }
+        }
+    }
+
+    fn load_state(&mut self) {
+        if let Some(path) = &self.save_state_path {
+            match fs::read(path) {
+                Ok(data) => {
+                    if let Err(error) = self.game_boy.load_state(&data) {
+                        log::warn!("Could not load the state: {:?}", error);
+                    }
+                }
+                Err(error) => log::warn!(
+                    "Could not read the state from {}: {:?}",
+                    path.display(),
+                    error
+                ),
+            }
+        }
+    }
+
+    fn flush_save_data(&mut self) {
+        if let (Some(file), Some(data)) = (
+            &mut self.save_data_file,
+            self.game_boy.cartridge().save_data(),
+        ) {
+            file.save(data);
+        }
+        self.save_data_flushed_time_ms = instant::now();
+    }
+
+    fn flush_save_data_periodically(&mut self) {
+        if instant::now() - self.save_data_flushed_time_ms >= SAVE_DATA_FLUSH_INTERVAL_MS {
+            self.flush_save_data();
+        }
+    }
+
+    fn resume(&mut self) {
+        self.processed_m_cycles = 0;
+        self.period_start_time_ms = instant::now();
+    }
+
+    fn set_button_state(&mut self, button_state: core::ButtonState) {
+        match &mut self.partner {
+            Some(partner) if self.controls_partner => {
+                partner.game_boy.set_button_state(button_state);
+                self.game_boy.set_button_state(Default::default());
+            }
+            _ => self.game_boy.set_button_state(button_state),
+        }
+    }
+
+    fn frame(&self) -> u64 {
+        self.elapsed_m_cycles / core::M_CYCLES_PER_FRAME
+    }
+
+    fn set_tilt(&mut self, tilt: core::Tilt) {
+        match &mut self.partner {
+            Some(partner) if self.controls_partner => {
+                partner.game_boy.set_tilt(tilt);
+                self.game_boy.set_tilt(Default::default());
+            }
+            _ => self.game_boy.set_tilt(tilt),
+        }
+    }
+
+    fn advance_cycles(
+        &mut self,
+        button_state: core::ButtonState,
+        tilt: core::Tilt,
+        serial_device: &mut SerialDevice,
+    ) -> Option<DebuggerEvent> {
+        self.set_button_state(button_state);
+        self.set_tilt(tilt);
+        if self.debugger.is_paused() {
+            self.resume();
+        }
         let current_time = instant::now();
         let target_m_cycles = ((core::M_CYCLES as f64) * (current_time - self.period_start_time_ms)
             / 1000f64)
             .floor() as u64;
-        while self.processed_m_cycles < target_m_cycles {
-            self.game_boy.tick(
-                &mut self.renderer,
-                &mut self.audio_output,
-                &mut core::NoSerialConnection,
-            );
+        let mut event = None;
+        while event.is_none() && self.processed_m_cycles < target_m_cycles {
+            event = match &mut self.partner {
+                Some(partner) => {
+                    let event = self.debugger.tick(
+                        &mut self.game_boy,
+                        &mut self.renderer,
+                        &mut self.audio_output,
+                        &mut LinkPort::new(&mut partner.game_boy),
+                    );
+                    partner.game_boy.tick(
+                        &mut partner.renderer,
+                        &mut core::NoAudioTerminal,
+                        &mut LinkPort::new(&mut self.game_boy),
+                    );
+                    event
+                }
+                None => self.debugger.tick(
+                    &mut self.game_boy,
+                    &mut self.renderer,
+                    &mut self.audio_output,
+                    serial_device,
+                ),
+            };
             self.processed_m_cycles += 1;
+            self.elapsed_m_cycles += 1;
         }
         while self.processed_m_cycles > core::M_CYCLES {
             self.processed_m_cycles -= core::M_CYCLES;
             self.period_start_time_ms += 1000f64;
         }
+        self.flush_save_data_periodically();
+        event
     }
 }
 
 pub struct App {
     state: Option<State>,
     texture: Option<egui::TextureHandle>,
+    partner_texture: Option<egui::TextureHandle>,
     receiver: command::Receiver,
+    serial_device: SerialDevice,
+    options: Options,
+    is_paused: bool,
+    debugger_panel: DebuggerPanel,
+    shows_debugger: bool,
+    cheats_window: CheatsWindow,
+    shows_cheats: bool,
+    ram_search_window: RamSearchWindow,
+    shows_ram_search: bool,
+}
+
+#[derive(Debug)]
+struct SaveDataFile {
+    path: PathBuf,
+    saved_data: Option<Vec<u8>>,
+}
+
+impl SaveDataFile {
+    fn new(rom_path: &Path) -> Self {
+        Self {
+            path: rom_path.with_extension("sav"),
+            saved_data: None,
+        }
+    }
+
+    fn load(&mut self) -> Option<Vec<u8>> {
+        let data = fs::read(&self.path).ok()?;
+        self.saved_data = Some(data.clone());
+        Some(data)
+    }
+
+    fn save(&mut self, data: Vec<u8>) {
+        if self.saved_data.as_ref() == Some(&data) {
+            return;
+        }
+        match fs::write(&self.path, &data) {
+            Ok(()) => self.saved_data = Some(data),
+            Err(error) => log::warn!(
+                "Could not write the save data to {}: {:?}",
+                self.path.display(),
+                error
+            ),
+        }
+    }
+}
+
+fn patch_rom(rom: &[u8], rom_path: &Path, patch_path: Option<&Path>) -> Option<Vec<u8>> {
+    let Some(patch_path) = patch_path
+        .map(Path::to_path_buf)
+        .or_else(|| core::find_patch(rom_path))
+    else {
+        return Some(rom.to_vec());
+    };
+    let patch = fs::read(&patch_path)
+        .map_err(|error| {
+            log::warn!(
+                "Could not read the patch from {}: {:?}",
+                patch_path.display(),
+                error
+            )
+        })
+        .ok()?;
+    let rom = core::apply_patch(rom, &patch)
+        .map_err(|error| {
+            log::warn!(
+                "Could not apply the patch {}: {:?}",
+                patch_path.display(),
+                error
+            )
+        })
+        .ok()?;
+    log::info!("Applied the patch: {}", patch_path.display());
+    Some(rom)
+}
+
+fn load_symbols(rom_path: &Path) -> Option<core::Symbols> {
+    let text = fs::read_to_string(rom_path.with_extension("sym")).ok()?;
+    text.parse()
+        .map_err(|error| log::warn!("Could not load the symbols: {}", error))
+        .ok()
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -74,33 +384,155 @@ fn dropped_file_bytes(file: &egui::DroppedFile) -> Option<Rc<Vec<u8>>> {
 }
 
 impl App {
-    pub fn new(receiver: command::Receiver) -> Self {
+    pub fn new(receiver: command::Receiver, link: Option<Link>, options: Options) -> Self {
         Self {
             state: None,
             texture: None,
+            partner_texture: None,
             receiver,
+            serial_device: match (link, &options.printer_path) {
+                (Some(link), _) => SerialDevice::Link(link),
+                (None, Some(path)) => {
+                    SerialDevice::Printer(Default::default(), PrintedImageWriter::new(path.clone()))
+                }
+                (None, None) => SerialDevice::None,
+            },
+            is_paused: options.starts_paused,
+            options,
+            debugger_panel: Default::default(),
+            shows_debugger: false,
+            cheats_window: Default::default(),
+            shows_cheats: false,
+            ram_search_window: Default::default(),
+            shows_ram_search: false,
         }
     }
 
-    pub fn boot(&mut self, rom: Rc<Vec<u8>>) {
-        self.state = State::new(rom)
+    pub fn boot(&mut self, rom: Rc<Vec<u8>>, rom_path: Option<PathBuf>) {
+        let rom = match &rom_path {
+            Some(path) => match patch_rom(&rom, path, self.options.patch_path.take().as_deref()) {
+                Some(rom) => Rc::new(rom),
+                None => return,
+            },
+            None => rom,
+        };
+        self.flush_save_data();
+        self.ram_search_window.reset();
+        self.state = State::new(rom, rom_path, &self.options)
     }
 
-    fn advance_cycles(&mut self, button_state: core::ButtonState) {
+    fn boot_file(&mut self, rom_path: PathBuf) {
+        match fs::read(&rom_path) {
+            Ok(bytes) => self.boot(Rc::new(bytes), Some(rom_path)),
+            Err(error) => log::warn!(
+                "Could not read the rom from {}: {:?}",
+                rom_path.display(),
+                error
+            ),
+        }
+    }
+
+    fn flush_save_data(&mut self) {
         if let Some(state) = &mut self.state {
-            state.advance_cycles(button_state);
+            state.flush_save_data();
+        }
+    }
+
+    fn advance_cycles(&mut self, button_state: core::ButtonState, tilt: core::Tilt) {
+        if self.is_paused {
+            return;
+        }
+        let event = self
+            .state
+            .as_mut()
+            .and_then(|state| state.advance_cycles(button_state, tilt, &mut self.serial_device));
+        self.serial_device.save_printed_images();
+        if let Some(event) = event {
+            self.debugger_panel.set_last_event(event);
+            self.shows_debugger = true;
+        }
+    }
+
+    fn process_debugger_key(&mut self, context: &egui::Context) {
+        if context.input().key_pressed(egui::Key::D) {
+            self.shows_debugger = !self.shows_debugger;
+        }
+    }
+
+    fn show_debugger(&mut self, context: &egui::Context) {
+        if let Some(state) = self.state.as_mut().filter(|_| self.shows_debugger) {
+            self.debugger_panel
+                .show(context, &mut state.game_boy, &mut state.debugger);
+        }
+    }
+
+    fn process_cheats_key(&mut self, context: &egui::Context) {
+        if context.input().key_pressed(egui::Key::C) {
+            self.shows_cheats = !self.shows_cheats;
+        }
+    }
+
+    fn show_cheats(&mut self, context: &egui::Context) {
+        if let Some(state) = self.state.as_mut().filter(|_| self.shows_cheats) {
+            self.cheats_window
+                .show(context, &mut state.game_boy, &mut self.shows_cheats);
+        }
+    }
+
+    fn process_ram_search_key(&mut self, context: &egui::Context) {
+        if context.input().key_pressed(egui::Key::R) {
+            self.shows_ram_search = !self.shows_ram_search;
+        }
+    }
+
+    fn show_ram_search(&mut self, context: &egui::Context) {
+        if let Some(state) = self.state.as_mut().filter(|_| self.shows_ram_search) {
+            self.ram_search_window
+                .show(context, &mut state.game_boy, &mut self.shows_ram_search);
+        }
+    }
+
+    fn is_editing(&self) -> bool {
+        self.debugger_panel.is_editing()
+            || self.cheats_window.is_editing()
+            || self.ram_search_window.is_editing()
+    }
+
+    fn process_pause_key(&mut self, context: &egui::Context) {
+        if context.input().key_pressed(egui::Key::P) {
+            self.is_paused = !self.is_paused;
+            if let Some(state) = self.state.as_mut().filter(|_| !self.is_paused) {
+                state.resume();
+            }
+        }
+    }
+
+    fn process_partner_key(&mut self, context: &egui::Context) {
+        if let Some(state) = self.state.as_mut().filter(|state| state.partner.is_some()) {
+            if context.input().key_pressed(egui::Key::Tab) {
+                state.controls_partner = !state.controls_partner;
+            }
+        }
+    }
+
+    fn process_state_keys(&mut self, context: &egui::Context) {
+        if let Some(state) = &mut self.state {
+            let input = context.input();
+            if input.key_pressed(egui::Key::S) {
+                state.save_state();
+            }
+            if input.key_pressed(egui::Key::L) {
+                state.load_state();
+            }
         }
     }
 
     fn process_dropped_file(&mut self, context: &egui::Context) {
-        if let Some(bytes) = context
-            .input()
-            .raw
-            .dropped_files
-            .first()
-            .and_then(|file| dropped_file_bytes(file))
-        {
-            self.boot(bytes);
+        let dropped_file = context.input().raw.dropped_files.first().cloned();
+        if let Some(file) = dropped_file {
+            if let Some(bytes) = dropped_file_bytes(&file) {
+                self.boot(bytes, file.path);
+            }
         }
     }
 
@@ -108,7 +540,8 @@ impl App {
         if let Ok(command) = self.receiver.try_recv() {
             use command::Command::*;
             match command {
-                Rom(bytes) => self.boot(Rc::new(bytes)),
+                Rom(bytes) => self.boot(Rc::new(bytes), None),
+                RomFile(path) => self.boot_file(path),
             }
         }
     }
@@ -131,25 +564,72 @@ fn button_state(context: &egui::Context) -> core::ButtonState {
 
 impl eframe::App for App {
     fn update(&mut self, context: &egui::Context, _frame: &mut eframe::Frame) {
-        self.advance_cycles(button_state(context));
+        if self.is_editing() {
+            self.advance_cycles(Default::default(), Default::default());
+        } else {
+            let frame = self.state.as_ref().map_or(0, State::frame);
+            let tilt = self.options.tilt_input.tilt(context, frame);
+            self.advance_cycles(button_state(context), tilt);
+        }
+        self.show_debugger(context);
+        self.show_cheats(context);
+        self.show_ram_search(context);
+        let is_editing = self.is_editing();
         egui::CentralPanel::default().show(context, |ui| {
             egui::Frame::default().show(ui, |ui| {
                 let texture = self.texture.get_or_insert_with(|| {
                     ui.ctx()
                         .load_texture("game-frame", Renderer::default_image())
                 });
+                let partner = self.state.as_ref().and_then(|state| state.partner.as_ref());
                 if let Some(state) = &self.state {
                     texture.set(state.renderer.image());
                 }
-                ui.image(texture, ui.max_rect().max - ui.max_rect().min)
-                    .request_focus();
+                let mut size = ui.max_rect().max - ui.max_rect().min;
+                let Some(partner) = partner else {
+                    let response = ui.image(texture, size);
+                    if !is_editing {
+                        response.request_focus();
+                    }
+                    return;
+                };
+                let partner_texture = self.partner_texture.get_or_insert_with(|| {
+                    ui.ctx()
+                        .load_texture("partner-frame", Renderer::default_image())
+                });
+                partner_texture.set(partner.renderer.image());
+                size.x /= 2.0;
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    let response = ui.image(texture, size);
+                    ui.image(partner_texture, size);
+                    if !is_editing {
+                        response.request_focus();
+                    }
+                });
             });
         });
+        if !is_editing {
+            self.process_pause_key(context);
+            self.process_debugger_key(context);
+            self.process_cheats_key(context);
+            self.process_ram_search_key(context);
+            self.process_partner_key(context);
+            self.process_state_keys(context);
+        }
         self.process_dropped_file(context);
         self.process_command();
         context.request_repaint();
     }
 
+    fn on_exit(&mut self, _gl: &eframe::glow::Context) {
+        self.flush_save_data();
+        if let SerialDevice::Printer(printer, _) = &mut self.serial_device {
+            printer.finish_page();
+        }
+        self.serial_device.save_printed_images();
+    }
+
     #[cfg(target_arch = "wasm32")]
     fn max_size_points(&self) -> egui::Vec2 {
         eframe::egui::Vec2::new(core::display_size().x as f32, core::display_size().y as f32)
@@ -0,0 +1,78 @@
+use core::{Cheat, GameBoy};
+use eframe::egui;
+
+#[derive(Debug, Default)]
+pub struct CheatsWindow {
+    input: String,
+    error: Option<String>,
+    is_editing: bool,
+}
+
+impl CheatsWindow {
+    pub fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    fn show_cheats(&mut self, ui: &mut egui::Ui, game_boy: &mut GameBoy) {
+        let mut toggled = None;
+        let mut removed = None;
+        for (index, cheat) in game_boy.cheats().iter().enumerate() {
+            ui.horizontal(|ui| {
+                let mut is_enabled = cheat.is_enabled;
+                if ui
+                    .checkbox(
+                        &mut is_enabled,
+                        egui::RichText::new(cheat.to_string()).monospace(),
+                    )
+                    .changed()
+                {
+                    toggled = Some((index, is_enabled));
+                }
+                if ui.small_button("x").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some((index, is_enabled)) = toggled {
+            game_boy.set_cheat_enabled(index, is_enabled);
+        }
+        if let Some(index) = removed {
+            game_boy.remove_cheat(index);
+        }
+    }
+
+    fn show_input(&mut self, ui: &mut egui::Ui, game_boy: &mut GameBoy) {
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut self.input);
+            self.is_editing = response.has_focus();
+            let is_submitted = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+            if ui.button("Add").clicked() || is_submitted {
+                match self.input.parse::<Cheat>() {
+                    Ok(cheat) => {
+                        game_boy.add_cheat(cheat);
+                        self.input.clear();
+                        self.error = None;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context, game_boy: &mut GameBoy, is_open: &mut bool) {
+        egui::Window::new("Cheats")
+            .open(is_open)
+            .resizable(false)
+            .show(context, |ui| {
+                self.show_cheats(ui, game_boy);
+                ui.separator();
+                self.show_input(ui, game_boy);
+            });
+        if !*is_open {
+            self.is_editing = false;
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use std::sync::mpsc;
+use std::{path::PathBuf, sync::mpsc};
 
 #[derive(Debug, Clone)]
 pub enum Command {
     Rom(Vec<u8>),
+    RomFile(PathBuf),
 }
 
 pub type Sender = mpsc::Sender<Command>;
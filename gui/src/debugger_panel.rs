@@ -0,0 +1,223 @@
+use core::{Breakpoint, Debugger, DebuggerCommand, DebuggerEvent, GameBoy, Watchpoint};
+use eframe::egui;
+
+const DISASSEMBLY_LINES: usize = 16;
+
+#[derive(Debug, Default)]
+pub struct DebuggerPanel {
+    breakpoint_input: String,
+    watchpoint_input: String,
+    cursor: Option<u16>,
+    last_event: Option<DebuggerEvent>,
+    is_editing: bool,
+}
+
+fn bank_prefix(game_boy: &GameBoy, address: u16) -> String {
+    match game_boy.rom_bank(address) {
+        Some(bank) => format!("{:02X}:", bank),
+        None => "   ".into(),
+    }
+}
+
+impl DebuggerPanel {
+    pub fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    pub fn set_last_event(&mut self, event: DebuggerEvent) {
+        self.last_event = Some(event);
+    }
+
+    fn show_controls(
+        &mut self,
+        ui: &mut egui::Ui,
+        game_boy: &mut GameBoy,
+        debugger: &mut Debugger,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            let mut command = None;
+            if debugger.is_paused() {
+                if ui.button("Continue").clicked() {
+                    command = Some(DebuggerCommand::Continue);
+                }
+            } else if ui.button("Pause").clicked() {
+                debugger.pause();
+            }
+            ui.add_enabled_ui(debugger.is_paused(), |ui| {
+                if ui.button("Step Into").clicked() {
+                    command = Some(DebuggerCommand::StepInto);
+                }
+                if ui.button("Step Over").clicked() {
+                    command = Some(DebuggerCommand::StepOver);
+                }
+                if ui.button("Step Out").clicked() {
+                    command = Some(DebuggerCommand::StepOut);
+                }
+                if let Some(cursor) = self.cursor {
+                    if ui.button("Run to Cursor").clicked() {
+                        let bank = game_boy.rom_bank(cursor);
+                        command = Some(DebuggerCommand::RunTo(Breakpoint::new(bank, cursor)));
+                    }
+                }
+            });
+            if let Some(command) = command {
+                self.last_event = None;
+                debugger.resume(game_boy, command);
+            }
+        });
+        match self.last_event {
+            Some(DebuggerEvent::Breakpoint(breakpoint)) => {
+                ui.label(format!("Breakpoint: {}", breakpoint));
+            }
+            Some(DebuggerEvent::Watchpoint { access, pc }) => {
+                ui.label(format!(
+                    "Watchpoint: {} by {}{:04X}",
+                    access,
+                    bank_prefix(game_boy, pc),
+                    pc
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn show_registers(&self, ui: &mut egui::Ui, game_boy: &GameBoy) {
+        let registers = game_boy.registers();
+        egui::Grid::new("registers").show(ui, |ui| {
+            for (name, value) in [
+                ("AF", registers.af()),
+                ("BC", registers.bc()),
+                ("DE", registers.de()),
+                ("HL", registers.hl()),
+                ("SP", registers.sp),
+                ("PC", registers.pc),
+            ] {
+                ui.monospace(name);
+                ui.monospace(format!("{:04X}", value));
+                ui.end_row();
+            }
+            ui.monospace("F");
+            ui.monospace(registers.f.to_string());
+            ui.end_row();
+        });
+    }
+
+    fn show_disassembly(&mut self, ui: &mut egui::Ui, game_boy: &mut GameBoy, debugger: &Debugger) {
+        let pc = game_boy.registers().pc;
+        let mut address = pc;
+        for _ in 0..DISASSEMBLY_LINES {
+            let disassembly = game_boy.disassemble(address);
+            let bank = game_boy.rom_bank(address);
+            if let Some(label) = game_boy.symbols().label(bank, address) {
+                ui.monospace(format!("{}:", label));
+            }
+            let has_breakpoint = debugger
+                .breakpoints()
+                .any(|breakpoint| *breakpoint == Breakpoint::new(bank, address));
+            let text = format!(
+                "{}{}{:04X} {}",
+                if address == pc { ">" } else { " " },
+                bank_prefix(game_boy, address),
+                address,
+                disassembly.text
+            );
+            let mut text = egui::RichText::new(text).monospace();
+            if has_breakpoint {
+                text = text.color(egui::Color32::RED);
+            }
+            if ui
+                .selectable_label(self.cursor == Some(address), text)
+                .clicked()
+            {
+                self.cursor = Some(address);
+            }
+            address = address.wrapping_add(disassembly.bytes.len() as u16);
+        }
+    }
+
+    fn show_breakpoints(&mut self, ui: &mut egui::Ui, game_boy: &GameBoy, debugger: &mut Debugger) {
+        let mut removed = None;
+        for breakpoint in debugger.breakpoints() {
+            ui.horizontal(|ui| {
+                ui.monospace(breakpoint.to_string());
+                if ui.small_button("x").clicked() {
+                    removed = Some(*breakpoint);
+                }
+            });
+        }
+        if let Some(breakpoint) = removed {
+            debugger.remove_breakpoint(&breakpoint);
+        }
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut self.breakpoint_input);
+            self.is_editing |= response.has_focus();
+            let is_submitted = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+            if ui.button("Add").clicked() || is_submitted {
+                match self.breakpoint_input.trim().parse::<Breakpoint>() {
+                    Ok(breakpoint) => {
+                        debugger.add_breakpoint(breakpoint);
+                        self.breakpoint_input.clear();
+                    }
+                    Err(error) => log::warn!("{}", error),
+                }
+            }
+        });
+        if let Some(cursor) = self.cursor {
+            if ui.button("Toggle Breakpoint at Cursor").clicked() {
+                let breakpoint = Breakpoint::new(game_boy.rom_bank(cursor), cursor);
+                if !debugger.remove_breakpoint(&breakpoint) {
+                    debugger.add_breakpoint(breakpoint);
+                }
+            }
+        }
+    }
+
+    fn show_watchpoints(&mut self, ui: &mut egui::Ui, game_boy: &mut GameBoy) {
+        let mut removed = None;
+        for watchpoint in game_boy.watcher_mut().watchpoints() {
+            ui.horizontal(|ui| {
+                ui.monospace(watchpoint.to_string());
+                if ui.small_button("x").clicked() {
+                    removed = Some(*watchpoint);
+                }
+            });
+        }
+        if let Some(watchpoint) = removed {
+            game_boy.watcher_mut().remove_watchpoint(&watchpoint);
+        }
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut self.watchpoint_input);
+            self.is_editing |= response.has_focus();
+            let is_submitted = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+            if ui.button("Watch").clicked() || is_submitted {
+                match self.watchpoint_input.parse::<Watchpoint>() {
+                    Ok(watchpoint) => {
+                        game_boy.watcher_mut().add_watchpoint(watchpoint);
+                        self.watchpoint_input.clear();
+                    }
+                    Err(error) => log::warn!("{}", error),
+                }
+            }
+        });
+    }
+
+    pub fn show(
+        &mut self,
+        context: &egui::Context,
+        game_boy: &mut GameBoy,
+        debugger: &mut Debugger,
+    ) {
+        egui::SidePanel::right("debugger").show(context, |ui| {
+            self.show_controls(ui, game_boy, debugger);
+            ui.separator();
+            self.show_registers(ui, game_boy);
+            ui.separator();
+            self.show_disassembly(ui, game_boy, debugger);
+            ui.separator();
+            self.is_editing = false;
+            self.show_breakpoints(ui, game_boy, debugger);
+            ui.separator();
+            self.show_watchpoints(ui, game_boy);
+        });
+    }
+}
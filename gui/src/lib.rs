@@ -1,29 +1,54 @@
 mod app;
 mod audio;
+mod cheats_window;
+mod debugger_panel;
+mod options;
+mod printer;
+mod ram_search_window;
 mod renderer;
+mod tilt;
 
 pub mod command;
 
+pub use options::{Options, Palette};
+pub use tilt::TiltInput;
+
 #[cfg(not(target_arch = "wasm32"))]
-pub fn start_native() {
-    let (_, receiver) = command::channels();
+pub fn start_native(
+    rom_path: Option<std::path::PathBuf>,
+    link: Option<core::SocketConnection>,
+    options: Options,
+) {
+    let (sender, receiver) = command::channels();
+    if let Some(rom_path) = rom_path {
+        _ = sender.send(command::Command::RomFile(rom_path));
+    }
     eframe::run_native(
         "u_u GB",
         eframe::NativeOptions {
             initial_window_size: Some(eframe::egui::Vec2::new(
-                core::display_size().x as f32 * 2.0,
-                core::display_size().y as f32 * 2.0,
+                core::display_size().x as f32
+                    * options.scale
+                    * if options.link_rom_path.is_some() {
+                        2.0
+                    } else {
+                        1.0
+                    },
+                core::display_size().y as f32 * options.scale,
             )),
             drag_and_drop_support: true,
             ..Default::default()
         },
-        Box::new(|_| Box::new(app::App::new(receiver))),
+        Box::new(move |_| Box::new(app::App::new(receiver, link, options))),
     );
 }
 
 #[cfg(target_arch = "wasm32")]
 pub fn start_wasm(canvas_id: &str) -> Result<command::Sender, eframe::wasm_bindgen::JsValue> {
     let (sender, receiver) = command::channels();
-    eframe::start_web(canvas_id, Box::new(|_| Box::new(app::App::new(receiver))))?;
+    eframe::start_web(
+        canvas_id,
+        Box::new(|_| Box::new(app::App::new(receiver, None, Default::default()))),
+    )?;
     Ok(sender)
 }
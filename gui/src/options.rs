@@ -0,0 +1,52 @@
+use crate::tilt::TiltInput;
+use std::{path::PathBuf, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Palette {
+    #[default]
+    Green,
+    Gray,
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "green" => Ok(Self::Green),
+            "gray" | "grey" => Ok(Self::Gray),
+            _ => Err(format!("Unknown palette: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub scale: f32,
+    pub palette: Palette,
+    pub is_muted: bool,
+    pub starts_paused: bool,
+    pub link_rom_path: Option<PathBuf>,
+    pub printer_path: Option<PathBuf>,
+    pub patch_path: Option<PathBuf>,
+    pub cheats: Vec<core::Cheat>,
+    pub rtc_clock: core::RtcClock,
+    pub tilt_input: TiltInput,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            scale: 2.0,
+            palette: Default::default(),
+            is_muted: false,
+            starts_paused: false,
+            link_rom_path: None,
+            printer_path: None,
+            patch_path: None,
+            cheats: vec![],
+            rtc_clock: Default::default(),
+            tilt_input: Default::default(),
+        }
+    }
+}
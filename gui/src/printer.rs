@@ -0,0 +1,73 @@
+use core::{Color, PrintedImage};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+#[derive(Debug)]
+pub struct PrintedImageWriter {
+    directory: PathBuf,
+    count: usize,
+}
+
+impl PrintedImageWriter {
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            count: 0,
+        }
+    }
+
+    fn next_path(&mut self) -> PathBuf {
+        loop {
+            self.count += 1;
+            let path = self.directory.join(format!("print-{}.png", self.count));
+            if !path.exists() {
+                return path;
+            }
+        }
+    }
+
+    pub fn save(&mut self, image: &PrintedImage) {
+        if let Err(error) = fs::create_dir_all(&self.directory) {
+            log::warn!(
+                "Could not create the directory {}: {:?}",
+                self.directory.display(),
+                error
+            );
+            return;
+        }
+        let path = self.next_path();
+        if let Err(error) = write_png(&path, image) {
+            log::warn!(
+                "Could not write the printed image to {}: {:?}",
+                path.display(),
+                error
+            );
+        }
+    }
+}
+
+fn write_png(path: &std::path::Path, image: &PrintedImage) -> Result<(), png::EncodingError> {
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        PrintedImage::WIDTH as u32,
+        image.height() as u32,
+    );
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data: Vec<u8> = image
+        .pixels()
+        .iter()
+        .map(|color| match color {
+            Color::White => 0xFF,
+            Color::LightGray => 0xAA,
+            Color::DarkGray => 0x55,
+            _ => 0x00,
+        })
+        .collect();
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()
+}
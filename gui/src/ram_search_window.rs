@@ -0,0 +1,106 @@
+use core::{GameBoy, RamSearch, RamSearchFilter, RamSearchValueType};
+use eframe::egui;
+
+const RESULT_COUNT: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct RamSearchWindow {
+    ram_search: Option<RamSearch>,
+    value_type: RamSearchValueType,
+    filter_input: String,
+    error: Option<String>,
+    is_editing: bool,
+}
+
+impl RamSearchWindow {
+    pub fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    pub fn reset(&mut self) {
+        self.ram_search = None;
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui, game_boy: &mut GameBoy) {
+        ui.horizontal(|ui| {
+            for (value_type, label) in [
+                (RamSearchValueType::Byte, "8-bit"),
+                (RamSearchValueType::WordLittleEndian, "16-bit LE"),
+                (RamSearchValueType::WordBigEndian, "16-bit BE"),
+            ] {
+                ui.radio_value(&mut self.value_type, value_type, label);
+            }
+            if ui.button("New Search").clicked() {
+                self.ram_search = Some(RamSearch::new(game_boy, self.value_type));
+                self.error = None;
+            }
+        });
+        let Some(ram_search) = &mut self.ram_search else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut self.filter_input);
+            self.is_editing = response.has_focus();
+            let is_submitted = response.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+            if ui.button("Filter").clicked() || is_submitted {
+                match self.filter_input.parse::<RamSearchFilter>() {
+                    Ok(filter) => {
+                        ram_search.filter(game_boy, filter);
+                        self.error = None;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+            }
+        });
+        ui.horizontal_wrapped(|ui| {
+            for (filter, label) in [
+                (RamSearchFilter::Unchanged, "Unchanged"),
+                (RamSearchFilter::Changed, "Changed"),
+                (RamSearchFilter::Decreased, "Decreased"),
+                (RamSearchFilter::Increased, "Increased"),
+            ] {
+                if ui.button(label).clicked() {
+                    ram_search.filter(game_boy, filter);
+                }
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    fn show_results(&self, ui: &mut egui::Ui, game_boy: &mut GameBoy) {
+        let Some(ram_search) = &self.ram_search else {
+            return;
+        };
+        ui.label(format!("{} candidates", ram_search.len()));
+        let width = ram_search.value_type().size() * 2;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("ram-search-results").show(ui, |ui| {
+                ui.label("Address");
+                ui.label("Value");
+                ui.label("Previous");
+                ui.end_row();
+                for result in ram_search.results(game_boy, RESULT_COUNT) {
+                    ui.monospace(result.address.to_string());
+                    ui.monospace(format!("{:0width$X}", result.current, width = width));
+                    ui.monospace(format!("{:0width$X}", result.previous, width = width));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    pub fn show(&mut self, context: &egui::Context, game_boy: &mut GameBoy, is_open: &mut bool) {
+        egui::Window::new("RAM Search")
+            .open(is_open)
+            .show(context, |ui| {
+                self.show_controls(ui, game_boy);
+                ui.separator();
+                self.show_results(ui, game_boy);
+            });
+        if !*is_open {
+            self.is_editing = false;
+        }
+    }
+}
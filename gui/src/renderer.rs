@@ -1,11 +1,21 @@
+use crate::options::Palette;
 use eframe::egui;
 
 pub struct Renderer {
+    palette: Palette,
     rendered_image: egui::ColorImage,
     rendering_image: egui::ColorImage,
 }
 
 impl Renderer {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            rendered_image: Self::default_image(),
+            rendering_image: Self::default_image(),
+        }
+    }
+
     pub fn image(&self) -> egui::ColorImage {
         self.rendered_image.clone()
     }
@@ -19,25 +29,24 @@ impl Renderer {
     }
 }
 
-impl Default for Renderer {
-    fn default() -> Self {
-        Self {
-            rendered_image: Self::default_image(),
-            rendering_image: Self::default_image(),
-        }
-    }
-}
-
 impl core::Renderer for Renderer {
     fn render(&mut self, position: core::Vec2, color: core::Color) {
         use core::Color::*;
         self.rendering_image.pixels
             [position.y as usize * core::display_size().x as usize + position.x as usize] =
-            match color {
-                White => egui::Color32::from_rgb(134, 163, 90),
-                LightGray => egui::Color32::from_rgb(111, 137, 79),
-                DarkGray => egui::Color32::from_rgb(88, 117, 79),
-                Black => egui::Color32::from_rgb(50, 84, 79),
+            match (self.palette, color) {
+                (Palette::Green, White) => egui::Color32::from_rgb(134, 163, 90),
+                (Palette::Green, LightGray) => egui::Color32::from_rgb(111, 137, 79),
+                (Palette::Green, DarkGray) => egui::Color32::from_rgb(88, 117, 79),
+                (Palette::Green, Black) => egui::Color32::from_rgb(50, 84, 79),
+                (Palette::Gray, White) => egui::Color32::from_gray(0xFF),
+                (Palette::Gray, LightGray) => egui::Color32::from_gray(0xAA),
+                (Palette::Gray, DarkGray) => egui::Color32::from_gray(0x55),
+                (Palette::Gray, Black) => egui::Color32::from_gray(0x00),
+                (_, Rgb(rgb)) => {
+                    let scale = |value: u8| (value as u16 * 0xFF / core::Rgb::MAX as u16) as u8;
+                    egui::Color32::from_rgb(scale(rgb.red), scale(rgb.green), scale(rgb.blue))
+                }
             };
         if position.x == core::display_size().x - 1 && position.y == core::display_size().y - 1 {
             std::mem::swap(&mut self.rendered_image, &mut self.rendering_image);
@@ -0,0 +1,56 @@
+use core::{Tilt, TiltScript, TiltSource};
+use eframe::egui;
+use std::{fs, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TiltInput {
+    #[default]
+    Mouse,
+    Keys,
+    Script(TiltScript),
+}
+
+impl FromStr for TiltInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mouse" => Ok(Self::Mouse),
+            "keys" => Ok(Self::Keys),
+            _ => fs::read_to_string(s)
+                .map_err(|error| format!("Could not read the tilt script {}: {}", s, error))?
+                .parse()
+                .map(Self::Script),
+        }
+    }
+}
+
+impl TiltInput {
+    pub fn tilt(&mut self, context: &egui::Context, frame: u64) -> Tilt {
+        match self {
+            Self::Mouse => mouse_tilt(context),
+            Self::Keys => key_tilt(context),
+            Self::Script(script) => script.tilt(frame),
+        }
+    }
+}
+
+fn mouse_tilt(context: &egui::Context) -> Tilt {
+    let input = context.input();
+    let Some(position) = input.pointer.hover_pos() else {
+        return Default::default();
+    };
+    let rect = input.screen_rect();
+    let offset = (position - rect.center()) / (rect.size() / 2.0);
+    Tilt::new(offset.x, offset.y)
+}
+
+fn key_tilt(context: &egui::Context) -> Tilt {
+    use egui::Key::*;
+    let keys = &context.input().keys_down;
+    let axis = |negative, positive| keys.contains(&positive) as i8 - keys.contains(&negative) as i8;
+    Tilt::new(
+        axis(ArrowLeft, ArrowRight) as f32,
+        axis(ArrowUp, ArrowDown) as f32,
+    )
+}
@@ -0,0 +1,344 @@
+use core::{Breakpoint, DebuggerCommand, DebuggerEvent, GameBoy, Symbols, Watchpoint};
+use std::{
+    io::{self, BufRead, Write},
+    process,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+const HELP: &str = "\
+s, step               Step into
+n, next               Step over
+f, finish             Step out
+c, continue           Continue (Ctrl-C pauses)
+u, until <ADDR>       Run to the address
+b, break <ADDR>       Add a breakpoint ([BANK:]ADDR or a label)
+d, delete <ADDR>      Remove a breakpoint
+bl, breakpoints       List breakpoints
+w, watch <RANGE>      Add a watchpoint (ADDR[-ADDR] [r|w|rw] [=VALUE])
+uw, unwatch <RANGE>   Remove a watchpoint
+wl, watchpoints       List watchpoints
+ch, cheat <CODE>      Add a Game Genie or GameShark code
+uc, uncheat <N>       Remove a cheat
+ce, enable <N>        Enable a cheat
+cd, disable <N>       Disable a cheat
+cl, cheats            List cheats
+rs, search [TYPE]     Start a RAM search (8, 16le or 16be)
+rf, filter <FILTER>   Narrow the RAM search (=, !=, <, > [VALUE], +N or -N)
+rr, results [N]       Show N RAM search results
+r, registers          Show registers
+l, list [ADDR] [N]    Disassemble N instructions
+x, examine <ADDR> [N] Show N bytes of memory
+h, help               Show this help
+q, quit               Quit";
+
+fn parse_address(word: Option<&str>, symbols: &Symbols) -> Result<u16, String> {
+    let word = word.ok_or("Missing address")?;
+    if let Some((_, address)) = symbols.address(word) {
+        return Ok(address);
+    }
+    u16::from_str_radix(word.trim_start_matches('$'), 16)
+        .map_err(|_| format!("Invalid address: {}", word))
+}
+
+fn parse_count(word: Option<&str>, default: usize) -> Result<usize, String> {
+    word.map_or(Ok(default), |word| {
+        word.parse().map_err(|_| format!("Invalid count: {}", word))
+    })
+}
+
+fn parse_cheat_index(word: Option<&str>, game_boy: &GameBoy) -> Result<usize, String> {
+    let word = word.ok_or("Missing cheat number")?;
+    match word.parse::<usize>() {
+        Ok(number) if (1..=game_boy.cheats().len()).contains(&number) => Ok(number - 1),
+        _ => Err(format!("No cheat {}", word)),
+    }
+}
+
+fn parse_breakpoint(word: Option<&str>, symbols: &Symbols) -> Result<Breakpoint, String> {
+    let word = word.ok_or("Missing address")?;
+    match symbols.address(word) {
+        Some((bank, address)) => Ok(Breakpoint::new(Some(bank), address)),
+        None => word.parse(),
+    }
+}
+
+fn parse_watchpoint<'a>(words: impl Iterator<Item = &'a str>) -> Result<Watchpoint, String> {
+    words.collect::<Vec<_>>().join(" ").parse()
+}
+
+fn format_address(game_boy: &GameBoy, address: u16) -> String {
+    match game_boy.rom_bank(address) {
+        Some(bank) => format!("{:02X}:{:04X}", bank, address),
+        None => format!("   {:04X}", address),
+    }
+}
+
+fn print_instruction(game_boy: &mut GameBoy, address: u16) -> u16 {
+    let disassembly = game_boy.disassemble(address);
+    if let Some(label) = game_boy.symbols().label(disassembly.bank, address) {
+        println!("{}:", label);
+    }
+    let bytes: Vec<String> = disassembly
+        .bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect();
+    println!(
+        "{} {} {:<8}  {}",
+        if address == game_boy.registers().pc {
+            "=>"
+        } else {
+            "  "
+        },
+        format_address(game_boy, address),
+        bytes.join(" "),
+        disassembly.text
+    );
+    disassembly.bytes.len() as u16
+}
+
+fn print_location(game_boy: &mut GameBoy) {
+    println!("{}", game_boy.registers());
+    print_instruction(game_boy, game_boy.registers().pc);
+}
+
+struct Session {
+    game_boy: GameBoy,
+    debugger: core::Debugger,
+    serial_connection: core::BufferedSerialConnection,
+    echoes_serial: bool,
+    ram_search: Option<core::RamSearch>,
+    is_running: Arc<AtomicBool>,
+}
+
+impl Session {
+    fn echo_serial(&mut self) {
+        if self.serial_connection.buffer().is_empty() {
+            return;
+        }
+        let text = self.serial_connection.take_text();
+        if self.echoes_serial && !text.is_empty() {
+            print!("{}", text);
+            _ = io::stdout().flush();
+        }
+    }
+
+    fn run(&mut self, command: DebuggerCommand) {
+        self.is_running.store(true, Ordering::SeqCst);
+        self.debugger.resume(&mut self.game_boy, command);
+        while !self.debugger.is_paused() {
+            if !self.is_running.load(Ordering::SeqCst) {
+                println!("Interrupted");
+                self.debugger.pause();
+                continue;
+            }
+            let event = self.debugger.tick(
+                &mut self.game_boy,
+                &mut core::NoRenderer,
+                &mut core::NoAudioTerminal,
+                &mut self.serial_connection,
+            );
+            self.echo_serial();
+            match event {
+                Some(DebuggerEvent::Breakpoint(breakpoint)) => {
+                    println!("Breakpoint: {}", breakpoint)
+                }
+                Some(DebuggerEvent::Watchpoint { access, pc }) => println!(
+                    "Watchpoint: {} by {}",
+                    access,
+                    format_address(&self.game_boy, pc)
+                ),
+                _ => {}
+            }
+        }
+        self.game_boy.flush_tracer();
+        self.is_running.store(false, Ordering::SeqCst);
+        print_location(&mut self.game_boy);
+    }
+
+    fn print_ram_search_results(&mut self, count: usize) -> Result<(), String> {
+        let ram_search = self.ram_search.as_ref().ok_or("No RAM search")?;
+        println!("{} candidates", ram_search.len());
+        let width = ram_search.value_type().size() * 2;
+        for result in ram_search.results(&mut self.game_boy, count) {
+            println!(
+                "{:>7}: {:0width$X} (previous: {:0width$X})",
+                result.address.to_string(),
+                result.current,
+                result.previous,
+                width = width
+            );
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, line: &str) -> Result<bool, String> {
+        let mut words = line.split_whitespace();
+        match words.next().unwrap_or_default() {
+            "s" | "step" => self.run(DebuggerCommand::StepInto),
+            "n" | "next" => self.run(DebuggerCommand::StepOver),
+            "f" | "finish" => self.run(DebuggerCommand::StepOut),
+            "c" | "continue" => self.run(DebuggerCommand::Continue),
+            "u" | "until" => self.run(DebuggerCommand::RunTo(parse_breakpoint(
+                words.next(),
+                self.game_boy.symbols(),
+            )?)),
+            "b" | "break" => self
+                .debugger
+                .add_breakpoint(parse_breakpoint(words.next(), self.game_boy.symbols())?),
+            "d" | "delete" => {
+                let breakpoint = parse_breakpoint(words.next(), self.game_boy.symbols())?;
+                if !self.debugger.remove_breakpoint(&breakpoint) {
+                    return Err(format!("No breakpoint at {}", breakpoint));
+                }
+            }
+            "bl" | "breakpoints" => {
+                for breakpoint in self.debugger.breakpoints() {
+                    println!("{}", breakpoint);
+                }
+            }
+            "w" | "watch" => self
+                .game_boy
+                .watcher_mut()
+                .add_watchpoint(parse_watchpoint(words)?),
+            "uw" | "unwatch" => {
+                let watchpoint = parse_watchpoint(words)?;
+                if !self.game_boy.watcher_mut().remove_watchpoint(&watchpoint) {
+                    return Err(format!("No watchpoint at {}", watchpoint));
+                }
+            }
+            "wl" | "watchpoints" => {
+                for watchpoint in self.game_boy.watcher_mut().watchpoints() {
+                    println!("{}", watchpoint);
+                }
+            }
+            "ch" | "cheat" => self
+                .game_boy
+                .add_cheat(words.next().ok_or("Missing cheat code")?.parse()?),
+            "uc" | "uncheat" => {
+                let index = parse_cheat_index(words.next(), &self.game_boy)?;
+                self.game_boy.remove_cheat(index);
+            }
+            "ce" | "enable" => {
+                let index = parse_cheat_index(words.next(), &self.game_boy)?;
+                self.game_boy.set_cheat_enabled(index, true);
+            }
+            "cd" | "disable" => {
+                let index = parse_cheat_index(words.next(), &self.game_boy)?;
+                self.game_boy.set_cheat_enabled(index, false);
+            }
+            "cl" | "cheats" => {
+                for (index, cheat) in self.game_boy.cheats().iter().enumerate() {
+                    println!(
+                        "{} [{}] {}",
+                        index + 1,
+                        if cheat.is_enabled { "x" } else { " " },
+                        cheat
+                    );
+                }
+            }
+            "rs" | "search" => {
+                let value_type = words.next().map_or(Ok(Default::default()), str::parse)?;
+                let ram_search = core::RamSearch::new(&mut self.game_boy, value_type);
+                println!("{} candidates", ram_search.len());
+                self.ram_search = Some(ram_search);
+            }
+            "rf" | "filter" => {
+                let filter = words.collect::<String>().parse()?;
+                let ram_search = self.ram_search.as_mut().ok_or("No RAM search")?;
+                ram_search.filter(&mut self.game_boy, filter);
+                self.print_ram_search_results(10)?;
+            }
+            "rr" | "results" => self.print_ram_search_results(parse_count(words.next(), 10)?)?,
+            "r" | "registers" => println!("{}", self.game_boy.registers()),
+            "l" | "list" => {
+                let mut address = match words.next() {
+                    Some(word) => parse_address(Some(word), self.game_boy.symbols())?,
+                    None => self.game_boy.registers().pc,
+                };
+                for _ in 0..parse_count(words.next(), 10)? {
+                    address = address.wrapping_add(print_instruction(&mut self.game_boy, address));
+                }
+            }
+            "x" | "examine" => {
+                let address = parse_address(words.next(), self.game_boy.symbols())?;
+                let count = parse_count(words.next(), 16)?;
+                for row in (0..count).step_by(16) {
+                    let start = address.wrapping_add(row as u16);
+                    let bytes: Vec<String> = (0..std::cmp::min(16, count - row))
+                        .map(|offset| {
+                            format!(
+                                "{:02X}",
+                                self.game_boy.read(start.wrapping_add(offset as u16))
+                            )
+                        })
+                        .collect();
+                    println!("{:04X}: {}", start, bytes.join(" "));
+                }
+            }
+            "h" | "help" => println!("{}", HELP),
+            "q" | "quit" => return Ok(false),
+            "" => {}
+            command => return Err(format!("Unknown command: {}", command)),
+        }
+        Ok(true)
+    }
+}
+
+pub fn run(
+    rom: Vec<u8>,
+    symbols: Symbols,
+    tracer: Option<core::Tracer>,
+    cheats: Vec<core::Cheat>,
+    echoes_serial: bool,
+    rtc_clock: core::RtcClock,
+) -> Result<(), core::CartridgeError> {
+    let mut cartridge = core::Cartridge::new(Rc::new(rom))?;
+    cartridge.set_rtc_clock(rtc_clock);
+    let mut session = Session {
+        game_boy: GameBoy::new(cartridge),
+        debugger: Default::default(),
+        serial_connection: Default::default(),
+        echoes_serial,
+        ram_search: None,
+        is_running: Default::default(),
+    };
+    let is_running = session.is_running.clone();
+    let interrupt = move || {
+        if !is_running.swap(false, Ordering::SeqCst) {
+            process::exit(130);
+        }
+    };
+    if let Err(error) = ctrlc::set_handler(interrupt) {
+        eprintln!("Could not handle Ctrl-C: {}", error);
+    }
+    session.game_boy.set_symbols(symbols);
+    session.game_boy.set_tracer(tracer);
+    for cheat in cheats {
+        session.game_boy.add_cheat(cheat);
+    }
+    print_location(&mut session.game_boy);
+    let mut last_line = String::new();
+    let stdin = io::stdin();
+    loop {
+        print!("(uugb) ");
+        _ = io::stdout().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            line = last_line.clone();
+        }
+        match session.execute(line.trim()) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(error) => eprintln!("{}", error),
+        }
+        last_line = line;
+    }
+}
@@ -0,0 +1,44 @@
+use core::{Disassembler, DisassemblerError, Disassembly, Symbols};
+
+pub struct Options {
+    pub bank: usize,
+    pub from: Option<u16>,
+    pub count: Option<usize>,
+    pub annotates: bool,
+}
+
+fn format_line(disassembly: &Disassembly) -> String {
+    let bytes: Vec<String> = disassembly
+        .bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect();
+    let line = format!(
+        "{:02X}:{:04X}  {:<8}  {}",
+        disassembly.bank.unwrap_or_default(),
+        disassembly.address,
+        bytes.join(" "),
+        disassembly.text
+    );
+    match &disassembly.comment {
+        Some(comment) => format!("{:<40} ; {}", line, comment),
+        None => line,
+    }
+}
+
+pub fn run(rom: &[u8], symbols: &Symbols, options: &Options) -> Result<(), DisassemblerError> {
+    let disassembler = Disassembler::new(rom)
+        .symbols(symbols)
+        .annotates(options.annotates);
+    let from = options
+        .from
+        .unwrap_or(*Disassembler::bank_addresses(options.bank).start());
+    let lines = disassembler.disassemble_bank(options.bank, from)?;
+    for disassembly in lines.iter().take(options.count.unwrap_or(lines.len())) {
+        if let Some(label) = symbols.label(disassembly.bank, disassembly.address) {
+            println!("{}:", label);
+        }
+        println!("{}", format_line(disassembly));
+    }
+    Ok(())
+}
@@ -0,0 +1,380 @@
+use core::TiltSource;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+const WAV_SAMPLE_RATE: u32 = 44100;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Cartridge(core::CartridgeError),
+    InvalidInput(usize, String),
+    Png(png::EncodingError),
+    Wav(hound::Error),
+    LinkDisconnected,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Cartridge(error) => write!(f, "Could not load the cartridge: {:?}", error),
+            Self::InvalidInput(line, content) => {
+                write!(f, "Invalid input at line {}: {}", line, content)
+            }
+            Self::Png(error) => write!(f, "Could not write the PNG: {}", error),
+            Self::Wav(error) => write!(f, "Could not write the WAV: {}", error),
+            Self::LinkDisconnected => write!(f, "The link peer closed the link"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<core::CartridgeError> for Error {
+    fn from(error: core::CartridgeError) -> Self {
+        Self::Cartridge(error)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(error: png::EncodingError) -> Self {
+        Self::Png(error)
+    }
+}
+
+impl From<hound::Error> for Error {
+    fn from(error: hound::Error) -> Self {
+        Self::Wav(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Duration {
+    Frames(u64),
+    Seconds(f64),
+}
+
+impl Duration {
+    fn m_cycles(&self) -> u64 {
+        match self {
+            Self::Frames(frames) => frames * core::M_CYCLES_PER_FRAME,
+            Self::Seconds(seconds) => (seconds * core::M_CYCLES as f64) as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub duration: Duration,
+    pub input: Option<PathBuf>,
+    pub screenshot: Option<PathBuf>,
+    pub audio: Option<PathBuf>,
+    pub echoes_serial: bool,
+    pub printer: Option<PathBuf>,
+    pub cheats: Vec<core::Cheat>,
+    pub tilt_script: Option<core::TiltScript>,
+    pub rtc_clock: core::RtcClock,
+    pub save_data: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct InputEvent {
+    frame: u64,
+    button_state: core::ButtonState,
+}
+
+fn parse_button_state(names: &[&str]) -> Option<core::ButtonState> {
+    let mut button_state = core::ButtonState::default();
+    for name in names {
+        let button = match name.to_ascii_lowercase().as_str() {
+            "up" => &mut button_state.up,
+            "down" => &mut button_state.down,
+            "left" => &mut button_state.left,
+            "right" => &mut button_state.right,
+            "a" => &mut button_state.a,
+            "b" => &mut button_state.b,
+            "start" => &mut button_state.start,
+            "select" => &mut button_state.select,
+            _ => return None,
+        };
+        *button = true;
+    }
+    Some(button_state)
+}
+
+fn load_input(path: &Path) -> Result<Vec<InputEvent>, Error> {
+    let mut events = vec![];
+    for (index, line) in std::fs::read_to_string(path)?.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((frame, names)) = words.split_first() else {
+            continue;
+        };
+        let invalid_input = || Error::InvalidInput(index + 1, line.trim().into());
+        events.push(InputEvent {
+            frame: frame.parse().map_err(|_| invalid_input())?,
+            button_state: parse_button_state(names).ok_or_else(invalid_input)?,
+        });
+    }
+    events.sort_by_key(|event| event.frame);
+    Ok(events)
+}
+
+struct FrameBuffer {
+    rendered_pixels: Vec<core::Color>,
+    rendering_pixels: Vec<core::Color>,
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        let size = core::display_size().x as usize * core::display_size().y as usize;
+        Self {
+            rendered_pixels: vec![core::Color::White; size],
+            rendering_pixels: vec![core::Color::White; size],
+        }
+    }
+}
+
+impl core::Renderer for FrameBuffer {
+    fn render(&mut self, position: core::Vec2, color: core::Color) {
+        self.rendering_pixels
+            [position.y as usize * core::display_size().x as usize + position.x as usize] = color;
+        if position.x == core::display_size().x - 1 && position.y == core::display_size().y - 1 {
+            std::mem::swap(&mut self.rendered_pixels, &mut self.rendering_pixels);
+        }
+    }
+}
+
+impl FrameBuffer {
+    fn save_png(&self, path: &Path) -> Result<(), Error> {
+        save_png(
+            path,
+            core::display_size().x as u32,
+            core::display_size().y as u32,
+            &self.rendered_pixels,
+        )
+    }
+}
+
+fn save_png(path: &Path, width: u32, height: u32, pixels: &[core::Color]) -> Result<(), Error> {
+    use core::Color::*;
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data: Vec<u8> = pixels
+        .iter()
+        .flat_map(|color| match color {
+            White => [0xFF, 0xFF, 0xFF],
+            LightGray => [0xAA, 0xAA, 0xAA],
+            DarkGray => [0x55, 0x55, 0x55],
+            Black => [0x00, 0x00, 0x00],
+            Rgb(rgb) => {
+                let scale = |value: u8| (value as u16 * 0xFF / core::Rgb::MAX as u16) as u8;
+                [scale(rgb.red), scale(rgb.green), scale(rgb.blue)]
+            }
+        })
+        .collect();
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+fn save_printed_images(directory: &Path, images: &[core::PrintedImage]) -> Result<(), Error> {
+    std::fs::create_dir_all(directory)?;
+    for (index, image) in images.iter().enumerate() {
+        save_png(
+            &directory.join(format!("print-{}.png", index + 1)),
+            core::PrintedImage::WIDTH as u32,
+            image.height() as u32,
+            image.pixels(),
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct AudioRecorder {
+    samples: Vec<core::AudioFrame>,
+    frame_counter: u64,
+}
+
+impl core::AudioTerminal for AudioRecorder {
+    fn output(&mut self, frame: core::AudioFrame) {
+        self.frame_counter += WAV_SAMPLE_RATE as u64;
+        while self.frame_counter >= core::AUDIO_SAMPLE_RATE {
+            self.frame_counter -= core::AUDIO_SAMPLE_RATE;
+            self.samples.push(frame);
+        }
+    }
+}
+
+impl AudioRecorder {
+    fn save_wav(&self, path: &Path) -> Result<(), Error> {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: WAV_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let sample = |source: u16| {
+            ((source as f32 / (core::MAX_AUDIO_FRAME_VOLUME as f32 / 2.0) - 1.0) * i16::MAX as f32)
+                as i16
+        };
+        let mut writer = hound::WavWriter::create(path, spec)?;
+        for frame in self.samples.iter() {
+            writer.write_sample(sample(frame.left))?;
+            writer.write_sample(sample(frame.right))?;
+        }
+        writer.finalize()?;
+        Ok(())
+    }
+}
+
+enum SerialDevice {
+    Buffer(core::BufferedSerialConnection),
+    Link(core::SocketConnection),
+    Printer(core::Printer),
+}
+
+impl core::SerialConnection for SerialDevice {
+    fn receive(&mut self, bit: bool) {
+        match self {
+            Self::Buffer(connection) => connection.receive(bit),
+            Self::Link(connection) => connection.receive(bit),
+            Self::Printer(connection) => connection.receive(bit),
+        }
+    }
+
+    fn send(&mut self) -> bool {
+        match self {
+            Self::Buffer(connection) => connection.send(),
+            Self::Link(connection) => connection.send(),
+            Self::Printer(connection) => connection.send(),
+        }
+    }
+
+    fn poll_clock(&mut self) -> bool {
+        match self {
+            Self::Buffer(connection) => connection.poll_clock(),
+            Self::Link(connection) => connection.poll_clock(),
+            Self::Printer(connection) => connection.poll_clock(),
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Self::Buffer(connection) = self {
+            connection.tick();
+        }
+    }
+}
+
+fn tick(
+    game_boy: &mut core::GameBoy,
+    frame_buffer: &mut FrameBuffer,
+    audio_recorder: &mut Option<AudioRecorder>,
+    serial_device: &mut SerialDevice,
+) {
+    match audio_recorder {
+        Some(audio_recorder) => game_boy.tick(frame_buffer, audio_recorder, serial_device),
+        None => game_boy.tick(frame_buffer, &mut core::NoAudioTerminal, serial_device),
+    }
+}
+
+pub fn run(
+    rom: Vec<u8>,
+    options: &Options,
+    tracer: Option<core::Tracer>,
+    link: Option<core::SocketConnection>,
+) -> Result<(), Error> {
+    let input = options
+        .input
+        .as_deref()
+        .map(load_input)
+        .transpose()?
+        .unwrap_or_default();
+    let mut cartridge = core::Cartridge::new(Rc::new(rom))?;
+    cartridge.set_rtc_clock(options.rtc_clock);
+    let save_data_path = options
+        .save_data
+        .as_ref()
+        .filter(|_| cartridge.has_battery());
+    if let Some(path) = save_data_path.filter(|path| path.exists()) {
+        cartridge.load_save_data(&fs::read(path)?);
+    }
+    let mut game_boy = core::GameBoy::new(cartridge);
+    game_boy.set_tracer(tracer);
+    for cheat in options.cheats.iter() {
+        game_boy.add_cheat(cheat.clone());
+    }
+    let mut frame_buffer = FrameBuffer::default();
+    let mut audio_recorder = options.audio.as_ref().map(|_| AudioRecorder::default());
+    let mut serial_device = match (link, &options.printer) {
+        (Some(link), _) => SerialDevice::Link(link),
+        (None, Some(_)) => SerialDevice::Printer(Default::default()),
+        (None, None) => SerialDevice::Buffer(Default::default()),
+    };
+    let echo_serial = |serial_device: &mut SerialDevice| {
+        let SerialDevice::Buffer(serial_connection) = serial_device else {
+            return;
+        };
+        let text = serial_connection.take_text();
+        if options.echoes_serial && !text.is_empty() {
+            print!("{}", text);
+            _ = io::stdout().flush();
+        }
+    };
+    let mut input = input.iter().peekable();
+    let mut tilt_script = options.tilt_script.clone();
+    for m_cycle in 0..options.duration.m_cycles() {
+        if m_cycle.is_multiple_of(core::M_CYCLES_PER_FRAME) {
+            echo_serial(&mut serial_device);
+            if let SerialDevice::Link(link) = &serial_device {
+                if !link.is_connected() {
+                    return Err(Error::LinkDisconnected);
+                }
+            }
+            let frame = m_cycle / core::M_CYCLES_PER_FRAME;
+            while let Some(event) = input.next_if(|event| event.frame <= frame) {
+                game_boy.set_button_state(event.button_state.clone());
+            }
+            if let Some(tilt_script) = tilt_script.as_mut() {
+                game_boy.set_tilt(tilt_script.tilt(frame));
+            }
+        }
+        tick(
+            &mut game_boy,
+            &mut frame_buffer,
+            &mut audio_recorder,
+            &mut serial_device,
+        );
+    }
+    echo_serial(&mut serial_device);
+    game_boy.flush_tracer();
+    if let (Some(directory), SerialDevice::Printer(printer)) =
+        (&options.printer, &mut serial_device)
+    {
+        printer.finish_page();
+        save_printed_images(directory, &printer.take_images())?;
+    }
+    if let (Some(path), Some(data)) = (save_data_path, game_boy.cartridge().save_data()) {
+        fs::write(path, data)?;
+    }
+    if let Some(path) = &options.screenshot {
+        frame_buffer.save_png(path)?;
+    }
+    if let (Some(path), Some(audio_recorder)) = (&options.audio, &audio_recorder) {
+        audio_recorder.save_wav(path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use core::SocketConnection;
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+pub fn listen(address: &str) -> io::Result<SocketConnection> {
+    println!("Waiting for the link on {}", address);
+    #[cfg(unix)]
+    if let Some(path) = address.strip_prefix("unix:") {
+        _ = std::fs::remove_file(path);
+        let (stream, _) = UnixListener::bind(path)?.accept()?;
+        return SocketConnection::from_unix(stream);
+    }
+    let (stream, _) = TcpListener::bind(address)?.accept()?;
+    SocketConnection::from_tcp(stream)
+}
+
+pub fn connect(address: &str) -> io::Result<SocketConnection> {
+    #[cfg(unix)]
+    if let Some(path) = address.strip_prefix("unix:") {
+        return SocketConnection::from_unix(UnixStream::connect(path)?);
+    }
+    SocketConnection::from_tcp(TcpStream::connect(address)?)
+}
@@ -2,15 +2,24 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod debugger;
+mod disasm;
+mod headless;
 mod info;
+mod link;
 
-use clap::Parser;
+use clap::{ArgGroup, Parser, Subcommand};
 use simplelog::*;
-use std::{fs::File, io::Read, path::Path, path::PathBuf};
+use std::{fs, fs::File, io::Read, ops::RangeInclusive, path::Path, path::PathBuf};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
+#[clap(group(ArgGroup::new("duration").args(&["frames", "seconds"])))]
+#[clap(group(ArgGroup::new("session").args(&["frames", "seconds", "debugger"])))]
+#[clap(group(ArgGroup::new("socket").args(&["link-listen", "link-connect"])))]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
     file: Option<PathBuf>,
     #[clap(long)]
     info: bool,
@@ -18,6 +27,112 @@ struct Args {
     logo: bool,
     #[clap(long)]
     debug: bool,
+    #[clap(long, requires = "file", conflicts_with = "duration")]
+    debugger: bool,
+    #[clap(long, requires = "file")]
+    patch: Option<PathBuf>,
+    #[clap(long, requires = "file")]
+    symbols: Option<PathBuf>,
+    #[clap(long, requires = "session")]
+    trace: Option<PathBuf>,
+    #[clap(long, requires = "trace", parse(try_from_str = parse_range))]
+    trace_pc: Option<RangeInclusive<u16>>,
+    #[clap(long, requires = "trace")]
+    trace_bank: Option<usize>,
+    #[clap(long, requires = "session", conflicts_with = "socket")]
+    serial: bool,
+    #[clap(long, requires = "file", conflicts_with_all = &["debugger", "socket"])]
+    link: Option<PathBuf>,
+    #[clap(long, requires = "file", conflicts_with = "debugger")]
+    link_listen: Option<String>,
+    #[clap(long, requires = "file", conflicts_with = "debugger")]
+    link_connect: Option<String>,
+    #[clap(long, requires = "file", conflicts_with_all = &["debugger", "socket", "link", "serial"])]
+    printer: Option<PathBuf>,
+    #[clap(
+        long = "cheat",
+        value_name = "CODE",
+        requires = "file",
+        multiple_occurrences = true
+    )]
+    cheats: Vec<core::Cheat>,
+    #[clap(long = "rtc", value_name = "CLOCK", default_value = "real")]
+    rtc_clock: core::RtcClock,
+    #[clap(long = "tilt", value_name = "SOURCE", default_value = "mouse")]
+    tilt_input: gui::TiltInput,
+    #[clap(long, requires = "file")]
+    frames: Option<u64>,
+    #[clap(long, requires = "file")]
+    seconds: Option<f64>,
+    #[clap(long, requires = "duration")]
+    input: Option<PathBuf>,
+    #[clap(long, requires = "duration")]
+    screenshot: Option<PathBuf>,
+    #[clap(long, requires = "duration")]
+    audio: Option<PathBuf>,
+    #[clap(long, default_value_t = 2.0)]
+    scale: f32,
+    #[clap(long, default_value = "green")]
+    palette: gui::Palette,
+    #[clap(long)]
+    mute: bool,
+    #[clap(long)]
+    paused: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    Disasm {
+        rom: PathBuf,
+        #[clap(long, default_value_t = 0)]
+        bank: usize,
+        #[clap(long, parse(try_from_str = parse_address))]
+        from: Option<u16>,
+        #[clap(long)]
+        count: Option<usize>,
+        #[clap(long)]
+        annotate: bool,
+        #[clap(long)]
+        symbols: Option<PathBuf>,
+    },
+}
+
+fn parse_address(value: &str) -> Result<u16, std::num::ParseIntError> {
+    u16::from_str_radix(value.trim_start_matches('$').trim_start_matches("0x"), 16)
+}
+
+fn parse_range(value: &str) -> Result<RangeInclusive<u16>, std::num::ParseIntError> {
+    Ok(match value.split_once('-') {
+        Some((start, end)) => parse_address(start)?..=parse_address(end)?,
+        None => parse_address(value)?..=parse_address(value)?,
+    })
+}
+
+fn create_tracer(args: &Args) -> Option<core::Tracer> {
+    let path = args.trace.as_ref()?;
+    let file = File::create(path).unwrap_or_else(|_err| {
+        eprintln!("Could not create the file: {}", path.display());
+        std::process::exit(1);
+    });
+    Some(core::Tracer::new(
+        file,
+        core::TraceFilter {
+            pc_range: args.trace_pc.clone(),
+            bank: args.trace_bank,
+        },
+    ))
+}
+
+fn open_link(args: &Args) -> Option<core::SocketConnection> {
+    let (address, result) = match (&args.link_listen, &args.link_connect) {
+        (Some(address), _) => (address, link::listen(address)),
+        (_, Some(address)) => (address, link::connect(address)),
+        _ => return None,
+    };
+    Some(result.unwrap_or_else(|err| {
+        eprintln!("Could not open the link on {}: {}", address, err);
+        std::process::exit(1);
+    }))
 }
 
 fn read_rom(filepath: &Path) -> Vec<u8> {
@@ -33,6 +148,45 @@ fn read_rom(filepath: &Path) -> Vec<u8> {
     rom
 }
 
+fn load_rom(filepath: &Path, patch_path: Option<&Path>) -> Vec<u8> {
+    let rom = read_rom(filepath);
+    let Some(patch_path) = patch_path
+        .map(Path::to_path_buf)
+        .or_else(|| core::find_patch(filepath))
+    else {
+        return rom;
+    };
+    let patch = fs::read(&patch_path).unwrap_or_else(|_err| {
+        eprintln!("Could not read the file: {}", patch_path.display());
+        std::process::exit(1);
+    });
+    core::apply_patch(&rom, &patch).unwrap_or_else(|err| {
+        eprintln!(
+            "Could not apply the patch {}: {:?}",
+            patch_path.display(),
+            err
+        );
+        std::process::exit(1);
+    })
+}
+
+fn load_symbols(path: Option<&Path>, rom_path: &Path) -> core::Symbols {
+    let default_path = rom_path.with_extension("sym");
+    let path = match path {
+        Some(path) => path,
+        None if default_path.exists() => &default_path,
+        None => return Default::default(),
+    };
+    let text = fs::read_to_string(path).unwrap_or_else(|_err| {
+        eprintln!("Could not read the file: {}", path.display());
+        std::process::exit(1);
+    });
+    text.parse().unwrap_or_else(|err| {
+        eprintln!("Could not load the symbols: {}", err);
+        std::process::exit(1);
+    })
+}
+
 fn load_header(rom: &[u8]) -> core::Header {
     core::Header::load(rom).unwrap_or_else(|err| {
         eprintln!("Could not load cartridge header: {:?}", err);
@@ -51,19 +205,108 @@ fn main() {
         )])
         .unwrap();
     }
+    if let Some(Command::Disasm {
+        rom,
+        bank,
+        from,
+        count,
+        annotate,
+        symbols,
+    }) = &args.command
+    {
+        let options = disasm::Options {
+            bank: *bank,
+            from: *from,
+            count: *count,
+            annotates: *annotate,
+        };
+        let symbols = load_symbols(symbols.as_deref(), rom);
+        disasm::run(&read_rom(rom), &symbols, &options).unwrap_or_else(|err| {
+            eprintln!("Could not disassemble the ROM: {:?}", err);
+            std::process::exit(1);
+        });
+        return;
+    }
     if args.info {
-        if let Some(filepath) = args.file {
-            let rom = read_rom(&filepath);
+        if let Some(filepath) = &args.file {
+            let rom = load_rom(filepath, args.patch.as_deref());
             info::print_cartridge_info(&load_header(&rom));
         }
         return;
     }
     if args.logo {
-        if let Some(filepath) = args.file {
-            let rom = read_rom(&filepath);
+        if let Some(filepath) = &args.file {
+            let rom = load_rom(filepath, args.patch.as_deref());
             println!("{}", load_header(&rom).logo.to_ascii_art());
         }
         return;
     }
-    gui::start_native();
+    if let (true, Some(filepath)) = (args.debugger, &args.file) {
+        let symbols = load_symbols(args.symbols.as_deref(), filepath);
+        debugger::run(
+            load_rom(filepath, args.patch.as_deref()),
+            symbols,
+            create_tracer(&args),
+            args.cheats.clone(),
+            args.serial,
+            args.rtc_clock,
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Could not load the cartridge: {:?}", err);
+            std::process::exit(1);
+        });
+        return;
+    }
+    let duration = match (args.frames, args.seconds) {
+        (Some(frames), _) => Some(headless::Duration::Frames(frames)),
+        (_, Some(seconds)) => Some(headless::Duration::Seconds(seconds)),
+        _ => None,
+    };
+    if let (Some(duration), Some(filepath)) = (duration, &args.file) {
+        let tracer = create_tracer(&args);
+        let link = open_link(&args);
+        let options = headless::Options {
+            duration,
+            input: args.input,
+            screenshot: args.screenshot,
+            audio: args.audio,
+            echoes_serial: args.serial,
+            printer: args.printer,
+            cheats: args.cheats,
+            tilt_script: match args.tilt_input {
+                gui::TiltInput::Script(script) => Some(script),
+                _ => None,
+            },
+            rtc_clock: args.rtc_clock,
+            save_data: Some(filepath.with_extension("sav")),
+        };
+        headless::run(
+            load_rom(filepath, args.patch.as_deref()),
+            &options,
+            tracer,
+            link,
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Could not run the ROM: {}", err);
+            std::process::exit(1);
+        });
+        return;
+    }
+    let link = open_link(&args);
+    gui::start_native(
+        args.file,
+        link,
+        gui::Options {
+            scale: args.scale,
+            palette: args.palette,
+            is_muted: args.mute,
+            starts_paused: args.paused,
+            link_rom_path: args.link,
+            printer_path: args.printer,
+            patch_path: args.patch,
+            cheats: args.cheats,
+            rtc_clock: args.rtc_clock,
+            tilt_input: args.tilt_input,
+        },
+    );
 }